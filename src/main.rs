@@ -1,20 +1,23 @@
-use std::{env, io::{stdin, stdout, Write, Read}, fmt::{Binary, Arguments}};
-use parse::regex::automaton::DFA;
+use std::{env, process, io::{stdin, stdout, Write}};
+use parse::regex::Regex;
 
 fn main(){
     let mut args = env::args();
     args.next();
     let re=args.next().expect("Expect regex");
-    let dfa=DFA::construct_dfa(&re).expect("Failed to construct dfa");
+    let regex=Regex::new(&re).unwrap_or_else(|error|{
+        eprintln!("{error}");
+        process::exit(1);
+    });
 
     let input=stdin();
     let mut output=stdout();
     
     for line in input.lines(){
         let line=line.expect("Failed to read line");
-        if dfa.match_input(&line){
+        if regex.is_match(&line){
             //output.write_fmt(format_args!("{line}\n")).expect("Failed to write to output");
-            write!(output,"{line}\n").expect("Failed to write to output");
+            writeln!(output,"{line}").expect("Failed to write to output");
         }
     }
 }
\ No newline at end of file
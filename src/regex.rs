@@ -1,34 +1,57 @@
+pub mod ast;
 pub mod automaton;
-mod transform;
+pub mod glob;
+pub mod printer;
+pub mod simplify;
+pub mod sql;
+pub mod transform;
+pub mod visit;
+mod error;
+mod matcher;
+mod unicode_tables;
+
+pub use error::{Error,ErrorKind};
+pub use matcher::{Captures,Match,Regex,RegexBuilder};
 
 
 #[cfg(test)]
 mod tests{
-    use super::{*, automaton::DFA};
+    use super::automaton::{DFA,NFA};
+    use super::transform::{parse_with_flags,Flags};
 
     #[test]
     fn regex_concat(){
-        let dfa=DFA::construct_dfa(&"abc".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("abc").unwrap();   
         assert!(dfa.match_input("abc"));
     }
 
+    #[test]
+    fn regex_search(){
+        let dfa=DFA::construct_dfa("abc").unwrap();   
+        assert!(dfa.match_input("xxabcxx"));
+        assert!(dfa.match_input("ababc"));
+        assert!(!dfa.match_input("axbc"));
+        assert!(!dfa.match_input("ab"));
+    }
+
     #[test]
     fn regex_or1(){
-        let dfa=DFA::construct_dfa(&"a|bc".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("a|bc").unwrap();   
         assert!(dfa.match_input("bc"));
-        assert!(dfa.match_input("ac"));
+        assert!(dfa.match_input("a"));
+        assert!(!dfa.match_input("c"));
     }
 
     #[test]
     fn regex_or2(){
-        let dfa=DFA::construct_dfa(&"(ab)|c".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("(ab)|c").unwrap();   
         assert!(dfa.match_input("ab"));
         assert!(dfa.match_input("c"));
     }
 
     #[test]
     fn regex_or3(){
-        let dfa=DFA::construct_dfa(&"(ab)|(cd)".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("(ab)|(cd)").unwrap();   
         assert!(dfa.match_input("ab"));
         assert!(dfa.match_input("cd"));
     }
@@ -36,36 +59,224 @@ mod tests{
 
     #[test]
     fn regex_asterisk1(){
-        let dfa=DFA::construct_dfa(&"a(bc)*|d".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("a(bc)*|d").unwrap();   
         assert!(dfa.match_input("abcbcbc"));
-        assert!(dfa.match_input("ad"));
+        assert!(dfa.match_input("d"));
     }
     
     #[test]
     fn regex_asterisk2(){
-        let dfa=DFA::construct_dfa(&"0a(bc)*|d(ef)d*".to_string()).unwrap();   
-        assert!(dfa.match_input("0adefdddd"));
-        assert!(dfa.match_input("0abcbcbcef"));
+        let dfa=DFA::construct_dfa("0a(bc)*|d(ef)d*").unwrap();   
+        assert!(dfa.match_input("defdddd"));
+        assert!(dfa.match_input("0abcbcbc"));
+        assert!(!dfa.match_input("dde"));
     }
         
     #[test]
     fn regex_asterisk3(){
-        let dfa=DFA::construct_dfa(&"(0a(bc)*|d(ef)d*)*(abc)|(123)".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("(0a(bc)*|d(ef)d*)*(abc)|(123)").unwrap();   
         assert!(dfa.match_input("0adefdddd0adefddddabc"));
-        assert!(dfa.match_input("0abcbcbcef0abcbcbcef123"));
+        assert!(dfa.match_input("0abcbcbcdef0abcbcbcdefabc"));
+        assert!(dfa.match_input("123"));
     }
         
     #[test]
     fn regex_incomplete1(){
-        let dfa=DFA::construct_dfa(&"(0a(bc)*|d(ef)d*)*(abc)|(123)".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("(0a(bc)*|d(ef)d*)*(abc)|(123)").unwrap();   
         assert!(dfa.match_input("0adefdddd0adefddddabcaaa"));
-        assert!(dfa.match_input("bbb0abcbcbcef0abcbcbcef123"));
+        assert!(dfa.match_input("bbb0abcbcbcdef0abcbcbcdefabc"));
+        assert!(dfa.match_input("bbb123"));
     }        
 
     #[test]
     fn regex_char_class_all1(){
-        let dfa=DFA::construct_dfa(&"(.*) and (.*)".to_string()).unwrap();   
+        let dfa=DFA::construct_dfa("(.*) and (.*)").unwrap();   
         assert!(dfa.match_input("(abc) and (123)"));
         assert!(dfa.match_input("(...) and (...)"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn regex_utf8(){
+        let dfa=DFA::construct_dfa("日本(語)*|é*ß").unwrap();   
+        assert!(dfa.match_input("日本語語"));
+        assert!(dfa.match_input("ééß"));
+        assert!(!dfa.match_input("日語"));
+    }
+
+    #[test]
+    fn regex_bracket1(){
+        let dfa=DFA::construct_dfa("id=[0-9a-f][0-9a-f]*;").unwrap();   
+        assert!(dfa.match_input("id=3fa9;"));
+        assert!(!dfa.match_input("id=;"));
+        assert!(!dfa.match_input("id=3g;"));
+    }
+
+    #[test]
+    fn regex_bracket2(){
+        let dfa=DFA::construct_dfa("<[^>]*>").unwrap();   
+        assert!(dfa.match_input("<日本 é>"));
+        assert!(!dfa.match_input("<a"));
+
+        let dfa=DFA::construct_dfa("[]-]").unwrap();   
+        assert!(dfa.match_input("]"));
+        assert!(dfa.match_input("-"));
+        assert!(!dfa.match_input("a"));
+    }
+
+    #[test]
+    fn regex_char_class_all2(){
+        let dfa=DFA::construct_dfa("a.c").unwrap();   
+        assert!(dfa.match_input("a日c"));
+        assert!(dfa.match_input("azc"));
+    }
+
+    #[test]
+    fn regex_perl_class1(){
+        let dfa=DFA::construct_dfa("\\d\\d*-\\w\\w*").unwrap();   
+        assert!(dfa.match_input("2024-error_log"));
+        assert!(dfa.match_input("٢٠٢٤-日志"));
+        assert!(!dfa.match_input("-abc"));
+    }
+
+    #[test]
+    fn regex_perl_class2(){
+        let ast=parse_with_flags("[\\d.]\\s\\S", Flags{unicode:false,..Flags::default()}).unwrap();
+        let dfa=DFA::from_nfa(&NFA::from_ast(&ast));   
+        assert!(dfa.match_input("1 x"));
+        assert!(dfa.match_input(".\tx"));
+        assert!(!dfa.match_input("٣ x"));
+        assert!(!dfa.match_input("1\u{A0}x"));
+    }
+
+    #[test]
+    fn regex_unicode_class1(){
+        let dfa=DFA::construct_dfa("\\p{Lu}\\p{Ll}*").unwrap();   
+        assert!(dfa.match_input("Ωμέγα"));
+        assert!(!dfa.match_input("ωμέγα"));
+
+        let dfa=DFA::construct_dfa("[\\p{Greek}\\p{Han}]\\P{L}").unwrap();   
+        assert!(dfa.match_input("語1"));
+        assert!(dfa.match_input("λ-"));
+        assert!(!dfa.match_input("λλ"));
+    }
+
+    #[test]
+    fn regex_plus_question(){
+        let dfa=DFA::construct_dfa("colou?r: [0-9]+").unwrap();   
+        assert!(dfa.match_input("color: 42"));
+        assert!(dfa.match_input("colour: 7"));
+        assert!(!dfa.match_input("colouur: 7"));
+        assert!(!dfa.match_input("colour: x"));
+    }
+
+    #[test]
+    fn regex_counted(){
+        let dfa=DFA::construct_dfa("x[0-9a-f]{2,4}y").unwrap();   
+        assert!(dfa.match_input("x0fy"));
+        assert!(dfa.match_input("xbeefy"));
+        assert!(!dfa.match_input("xfy"));
+        assert!(!dfa.match_input("xc0ffeey"));
+
+        let dfa=DFA::construct_dfa("(ab){2}x|c{3,}").unwrap();   
+        assert!(dfa.match_input("ababx"));
+        assert!(dfa.match_input("ccccc"));
+        assert!(!dfa.match_input("abx"));
+        assert!(!dfa.match_input("cc"));
+    }
+
+    #[test]
+    fn regex_anchor1(){
+        let dfa=DFA::construct_dfa("^ab|c$").unwrap();   
+        assert!(dfa.match_input("abx"));
+        assert!(dfa.match_input("xxc"));
+        assert!(!dfa.match_input("xab"));
+        assert!(!dfa.match_input("cx"));
+
+        let dfa=DFA::construct_dfa("\\A[0-9]+\\z").unwrap();   
+        assert!(dfa.match_input("2024"));
+        assert!(!dfa.match_input("2024x"));
+        assert!(!dfa.match_input("x2024"));
+        assert!(!dfa.match_input(""));
+
+        let dfa=DFA::construct_dfa("^$").unwrap();   
+        assert!(dfa.match_input(""));
+        assert!(!dfa.match_input("\n"));
+    }
+
+    #[test]
+    fn regex_anchor2(){
+        let multi_line=Flags{multi_line:true,..Flags::default()};
+        let dfa=DFA::from_nfa(&NFA::from_ast(&parse_with_flags("^b+$", multi_line).unwrap()));   
+        assert!(dfa.match_input("a\nbb\nc"));
+        assert!(dfa.match_input("a\nb"));
+        assert!(!dfa.match_input("a\nbc"));
+        assert!(!dfa.match_input("ab\nc"));
+
+        let dfa=DFA::from_nfa(&NFA::from_ast(&parse_with_flags("\\A^x$", multi_line).unwrap()));   
+        assert!(dfa.match_input("x\ny"));
+        assert!(!dfa.match_input("y\nx"));
+    }
+
+    #[test]
+    fn regex_word_boundary(){
+        let dfa=DFA::construct_dfa("\\bid\\b").unwrap();   
+        assert!(dfa.match_input("let id = 3;"));
+        assert!(dfa.match_input("id"));
+        assert!(!dfa.match_input("let ids = 3;"));
+        assert!(!dfa.match_input("grid"));
+        assert!(!dfa.match_input("idé"));
+
+        let dfa=DFA::construct_dfa("\\Bor\\B").unwrap();   
+        assert!(dfa.match_input("word"));
+        assert!(!dfa.match_input("or for ore"));
+
+        let ascii=Flags{unicode:false,..Flags::default()};
+        let dfa=DFA::from_nfa(&NFA::from_ast(&parse_with_flags("\\bid\\b", ascii).unwrap()));   
+        assert!(dfa.match_input("idé"));
+        assert!(!dfa.match_input("_id"));
+    }
+
+    #[test]
+    fn regex_case_insensitive(){
+        let dfa=DFA::construct_dfa("(?i)error|warn").unwrap();   
+        assert!(dfa.match_input("ERROR"));
+        assert!(dfa.match_input("eRrOr"));
+        assert!(dfa.match_input("WARN"));
+
+        let dfa=DFA::construct_dfa("a((?i)b)c").unwrap();   
+        assert!(dfa.match_input("aBc"));
+        assert!(!dfa.match_input("aBC"));
+        assert!(!dfa.match_input("ABc"));
+
+        let dfa=DFA::construct_dfa("(?i)σ+(?-i)x").unwrap();   
+        assert!(dfa.match_input("σσςx"));
+        assert!(dfa.match_input("Σςx"));
+        assert!(!dfa.match_input("ΣX"));
+    }
+
+    #[test]
+    fn regex_dot_new_line(){
+        let dfa=DFA::construct_dfa("a.c").unwrap();   
+        assert!(dfa.match_input("abc"));
+        assert!(!dfa.match_input("a\nc"));
+
+        let dfa=DFA::construct_dfa("(?s)a.c").unwrap();   
+        assert!(dfa.match_input("a\nc"));
+
+        let dfa=DFA::construct_dfa("(?m:^b$)|\\Ax").unwrap();   
+        assert!(dfa.match_input("a\nb\nc"));
+        assert!(!dfa.match_input("a\nab\nc"));
+    }
+
+    #[test]
+    fn regex_class_set(){
+        let dfa=DFA::construct_dfa("^[[:alpha:]_][[:alnum:]_]*=[\\p{L}&&[^a-z]]+$").unwrap();   
+        assert!(dfa.match_input("_id2=ÉTÉ"));
+        assert!(!dfa.match_input("_id2=Été"));
+        assert!(!dfa.match_input("2id=A"));
+
+        let dfa=DFA::construct_dfa("^[0-9a-f~~a-z]+$").unwrap();   
+        assert!(dfa.match_input("09xyz"));
+        assert!(!dfa.match_input("0a"));
+    }
+}
@@ -0,0 +1,214 @@
+pub use super::automaton::{Class,ClassSet,ClassRange,Look,PerlClass,PerlClassKind,UnicodeClass,UnicodeProperty};
+
+/*
+Typed syntax tree produced by transform::parse and consumed by the NFA compiler.
+Concat and Alternation always hold at least two items, use Ast::concat and
+Ast::alternation to build them from arbitrary vectors.
+*/
+#[derive(Clone,Debug,PartialEq)]
+pub enum Ast {
+    Empty,
+    Literal(char),
+    Class(Class),
+    Look(Look),
+    Concat(Vec<Ast>),
+    Alternation(Vec<Ast>),
+    Repetition(Repetition),
+    Group(Group),
+    Backreference(Backreference),
+    LookAround(LookAround),
+    /*(?>...), once the group matched the match is kept and never backtracked into*/
+    Atomic(Box<Ast>),
+}
+
+/*
+Capture group, groups are numbered from 1 in the order their '(' appears
+and index 0 stands for the whole match
+*/
+#[derive(Clone,Debug,PartialEq)]
+pub struct Group{
+    pub index:usize,
+    pub name:Option<String>,
+    pub ast:Box<Ast>
+}
+
+/*
+\1 or \k<name>, matches the text the group last captured and fails while the group hasn't matched,
+case insensitive backreferences compare chars under simple case folding
+*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq)]
+pub struct Backreference{
+    pub index:usize,
+    pub case_insensitive:bool
+}
+
+/*
+(?=...) (?!...) (?<=...) (?<!...), zero width assertions about the text after or before the position,
+look-behinds have a bounded length
+*/
+#[derive(Clone,Debug,PartialEq)]
+pub struct LookAround{
+    pub kind:LookAroundKind,
+    pub ast:Box<Ast>
+}
+
+#[derive(Clone,Copy,Debug,PartialEq, Eq)]
+pub enum LookAroundKind{
+    Ahead,
+    NotAhead,
+    Behind,
+    NotBehind
+}
+
+#[derive(Clone,Debug,PartialEq)]
+pub struct Repetition{
+    pub kind:RepetitionKind,
+    /*lazy repetitions written with a trailing '?' prefer repeating as few times as possible*/
+    pub greedy:bool,
+    pub ast:Box<Ast>
+}
+
+/*
+Counted forms keep their bounds as written, {n,m} always has n<=m
+*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq)]
+pub enum RepetitionKind{
+    ZeroOrMore,
+    OneOrMore,
+    ZeroOrOne,
+    Exactly(u32),
+    AtLeast(u32),
+    Bounded(u32,u32)
+}
+
+impl RepetitionKind {
+    /*least and most copies, None when there is no upper bound*/
+    pub fn bounds(&self)->(u32,Option<u32>){
+        match *self {
+            RepetitionKind::ZeroOrMore=>(0,None),
+            RepetitionKind::OneOrMore=>(1,None),
+            RepetitionKind::ZeroOrOne=>(0,Some(1)),
+            RepetitionKind::Exactly(n)=>(n,Some(n)),
+            RepetitionKind::AtLeast(n)=>(n,None),
+            RepetitionKind::Bounded(n,m)=>(n,Some(m)),
+        }
+    }
+}
+
+impl Ast {
+    pub fn concat(mut items:Vec<Ast>)->Ast{
+        match items.len() {
+            0=>Ast::Empty,
+            1=>items.pop().expect("checked length"),
+            _=>Ast::Concat(items)
+        }
+    }
+
+    pub fn alternation(mut items:Vec<Ast>)->Ast{
+        match items.len() {
+            0=>Ast::Empty,
+            1=>items.pop().expect("checked length"),
+            _=>Ast::Alternation(items)
+        }
+    }
+
+    pub fn repetition(kind:RepetitionKind,ast:Ast)->Ast{
+        Ast::Repetition(Repetition{kind,greedy:true,ast:Box::new(ast)})
+    }
+
+    pub fn lazy_repetition(kind:RepetitionKind,ast:Ast)->Ast{
+        Ast::Repetition(Repetition{kind,greedy:false,ast:Box::new(ast)})
+    }
+
+    pub fn group(index:usize,ast:Ast)->Ast{
+        Ast::Group(Group{index,name:None,ast:Box::new(ast)})
+    }
+
+    pub fn named_group(index:usize,name:&str,ast:Ast)->Ast{
+        Ast::Group(Group{index,name:Some(name.to_string()),ast:Box::new(ast)})
+    }
+
+    pub fn backreference(index:usize)->Ast{
+        Ast::Backreference(Backreference{index,case_insensitive:false})
+    }
+
+    pub fn look_around(kind:LookAroundKind,ast:Ast)->Ast{
+        Ast::LookAround(LookAround{kind,ast:Box::new(ast)})
+    }
+
+    pub fn atomic(ast:Ast)->Ast{
+        Ast::Atomic(Box::new(ast))
+    }
+
+    /*backreferences, look-arounds and atomic groups can't be compiled to an automaton and need the backtracking matcher*/
+    pub fn needs_backtracking(&self)->bool{
+        match self {
+            Ast::Empty|Ast::Literal(_)|Ast::Class(_)|Ast::Look(_)=>false,
+            Ast::Concat(items)|Ast::Alternation(items)=>items.iter().any(Ast::needs_backtracking),
+            Ast::Repetition(repetition)=>repetition.ast.needs_backtracking(),
+            Ast::Group(group)=>group.ast.needs_backtracking(),
+            Ast::Backreference(_)|Ast::LookAround(_)|Ast::Atomic(_)=>true,
+        }
+    }
+
+    /*fewest and most chars a match can span, None when there is no upper bound*/
+    pub fn length_bounds(&self)->(usize,Option<usize>){
+        match self {
+            Ast::Empty|Ast::Look(_)|Ast::LookAround(_)=>(0,Some(0)),
+            Ast::Literal(_)|Ast::Class(_)=>(1,Some(1)),
+            Ast::Concat(items)=>{
+                items
+                .iter()
+                .map(Ast::length_bounds)
+                .fold((0,Some(0)),|(min,max),(item_min,item_max)|{
+                    (min.saturating_add(item_min),max.zip(item_max).map(|(max,item_max)| max.saturating_add(item_max)))
+                })
+            },
+            Ast::Alternation(items)=>{
+                let bounds:Vec<_>=items.iter().map(Ast::length_bounds).collect();
+                let min=bounds.iter().map(|(min,_)| *min).min().unwrap_or(0);
+                let max=bounds.iter().try_fold(0,|max,(_,item_max)| item_max.map(|item_max| max.max(item_max)));
+                (min,max)
+            },
+            Ast::Repetition(repetition)=>{
+                let (min,max)=repetition.ast.length_bounds();
+                let (times_min,times_max)=repetition.kind.bounds();
+                let max=match (max,times_max) {
+                    (Some(0),_)=>Some(0),
+                    (Some(max),Some(times_max))=>Some(max.saturating_mul(times_max as usize)),
+                    _=>None
+                };
+                (min.saturating_mul(times_min as usize),max)
+            },
+            Ast::Group(group)=>group.ast.length_bounds(),
+            Ast::Atomic(ast)=>ast.length_bounds(),
+            Ast::Backreference(_)=>(0,None),
+        }
+    }
+
+    /*name of every group by index, the whole match at index 0 has none*/
+    pub fn capture_names(&self)->Vec<Option<String>>{
+        let mut names=vec![None];
+        self.collect_capture_names(&mut names);
+        names
+    }
+
+    fn collect_capture_names(&self,names:&mut Vec<Option<String>>){
+        match self {
+            Ast::Empty|Ast::Literal(_)|Ast::Class(_)|Ast::Look(_)|Ast::Backreference(_)=>{},
+            Ast::Concat(items)|Ast::Alternation(items)=>{
+                items.iter().for_each(|item| item.collect_capture_names(names));
+            },
+            Ast::Repetition(repetition)=>repetition.ast.collect_capture_names(names),
+            Ast::LookAround(look_around)=>look_around.ast.collect_capture_names(names),
+            Ast::Atomic(ast)=>ast.collect_capture_names(names),
+            Ast::Group(group)=>{
+                if names.len()<=group.index {
+                    names.resize(group.index+1, None);
+                }
+                names[group.index]=group.name.clone();
+                group.ast.collect_capture_names(names);
+            },
+        }
+    }
+}
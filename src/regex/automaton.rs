@@ -1,13 +1,17 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet,HashSet};
 use std::fmt::Debug;
 
 mod state;
+pub mod backtrack;
 pub mod dfa;
 pub mod nfa;
 
+pub use backtrack::Backtracker;
 pub use dfa::DFA;
 pub use nfa::NFA;
 
+pub use state::{Class,ClassSet,ClassRange,Look,PerlClass,PerlClassKind,UnicodeClass,UnicodeProperty};
+
 use state::{State,StateRef};
 
 use self::state::Symbol;
@@ -29,6 +33,15 @@ impl Automaton {
         }
     }
 
+    fn automaton_empty()->Automaton{
+        let end=State::new_accept_ref();
+        let transition=State::new_transition_ref(Symbol::Epsilon,Some(end.clone()));
+        Automaton{
+            start_state:transition,
+            end_state:end
+        }
+    }
+
     fn automaton_transition(symbol:&Symbol)->Automaton{
         let end=State::new_accept_ref();
         let transition=State::new_transition_ref(symbol.clone(),Some(end.clone()));
@@ -54,31 +67,151 @@ impl Automaton {
         }
     }
     
-    fn automaton_zero_or_one(automaton:&Automaton)->Automaton{
+    /*the slots of group index are saved around the automaton*/
+    fn automaton_capture(automaton:&Automaton,index:usize)->Automaton{
+        let open=Automaton::automaton_transition(&Symbol::Save(2*index));
+        let close=Automaton::automaton_transition(&Symbol::Save(2*index+1));
+        let open=Automaton::automaton_concat(&open, automaton);
+        Automaton::automaton_concat(&open, &close)
+    }
+
+    /*
+    The first edge of a split is the preferred one, greedy repetitions prefer
+    entering the automaton again and lazy ones prefer leaving
+    */
+    fn split_edges(repeat:&StateRef,leave:&StateRef,greedy:bool)->(Option<StateRef>,Option<StateRef>){
+        match greedy {
+            true=>(Some(repeat.clone()), Some(leave.clone())),
+            false=>(Some(leave.clone()), Some(repeat.clone()))
+        }
+    }
+
+    /*
+    the end loops back to the split itself, so a pass through the automaton that matched nothing
+    reaches a state its thread already saw and is dropped instead of leaving before the other paths
+    */
+    fn automaton_zero_or_more(automaton:&Automaton,greedy:bool)->Automaton{
         let new_end=State::new_accept_ref();
-        let new_start=State::new_split_ref(Some(automaton.start_state.clone()), Some(new_end.clone()));
-        *(*automaton.end_state).borrow_mut()=State::new_split(Some(automaton.start_state.clone()), Some(new_end.clone()));
+        let (to1,to2)=Automaton::split_edges(&automaton.start_state, &new_end, greedy);
+        let new_start=State::new_split_ref(to1, to2);
+        (*automaton.end_state).borrow_mut().connect(&new_start);
         Automaton{
             start_state:new_start,
             end_state:new_end
         }
     }
 
-    pub fn get_alphabet(&self)->HashSet<char>{
-        let flatten_graph=Vec::from(self.clone());
+    /*
+    the end leads to a split back to the start, a later pass that matched nothing is dropped the same way.
+    The first pass may be empty, (?:a*?)+ matches the empty string
+    */
+    fn automaton_one_or_more(automaton:&Automaton,greedy:bool)->Automaton{
+        let new_end=State::new_accept_ref();
+        let (to1,to2)=Automaton::split_edges(&automaton.start_state, &new_end, greedy);
+        *(*automaton.end_state).borrow_mut()=State::new_split(to1, to2);
+        Automaton{
+            start_state:automaton.start_state.clone(),
+            end_state:new_end
+        }
+    }
+
+    fn automaton_zero_or_one(automaton:&Automaton,greedy:bool)->Automaton{
+        let new_end=State::new_accept_ref();
+        let (to1,to2)=Automaton::split_edges(&automaton.start_state, &new_end, greedy);
+        let new_start=State::new_split_ref(to1, to2);
+        (*automaton.end_state).borrow_mut().connect(&new_end);
+        Automaton{
+            start_state:new_start,
+            end_state:new_end
+        }
+    }
 
+    /*
+    min copies followed by a loop when max is None, otherwise by max-min nested optional copies x(x(x)?)?)?.
+    Every copy needs its own states so the copies come from calling automaton again
+    */
+    fn automaton_repeat<F:Fn()->Automaton>(automaton:F,min:u32,max:Option<u32>,greedy:bool)->Automaton{
+        let mut copies:Vec<Automaton>=(0..min).map(|_| automaton()).collect();
+        match max {
+            None=>{
+                let tail=match copies.pop() {
+                    Some(last)=>Automaton::automaton_one_or_more(&last,greedy),
+                    None=>Automaton::automaton_zero_or_more(&automaton(),greedy)
+                };
+                copies.push(tail);
+            }
+            Some(max)=>{
+                let mut optional:Option<Automaton>=None;
+                for _ in min..max{
+                    let copy=automaton();
+                    let copy=match optional {
+                        Some(inner)=>Automaton::automaton_concat(&copy, &inner),
+                        None=>copy
+                    };
+                    optional=Some(Automaton::automaton_zero_or_one(&copy,greedy));
+                }
+                copies.extend(optional);
+            }
+        }
+
+        copies
+        .into_iter()
+        .reduce(|automaton1,automaton2| Automaton::automaton_concat(&automaton1, &automaton2))
+        .unwrap_or_else(Automaton::automaton_empty)
+    }
+
+    /*
+    Splits the chars used by the transitions or told apart by looks into disjoint ranges,
+    every transition symbol either contains a whole range or none of it
+    */
+    pub fn get_alphabet(&self)->Vec<ClassRange>{
+        let flatten_graph=Vec::from(self.clone());
+        let sets:Vec<ClassSet>=
         flatten_graph
         .iter()
         .flat_map(|state| state.adjacent())
-        .map(|(symbol,_)|symbol)
-        .filter_map(|symbol| match symbol {
-            Symbol::Alphabet(c) => Some(c),
-            Symbol::CharClass(_)=>None,
-            Symbol::Epsilon => None,
+        .flat_map(|(symbol,_)|{
+            match symbol {
+                Symbol::Look(look)=>look.char_sets(),
+                _=>symbol.to_set().into_iter().collect()
+            }
         })
+        .collect();
+
+        let boundaries:BTreeSet<char>=
+        sets
+        .iter()
+        .flat_map(|set| set.ranges().iter())
+        .flat_map(|range| [Some(range.start),state::next_char(range.end)])
+        .flatten()
+        .collect();
+
+        let boundaries:Vec<char>=boundaries.into_iter().collect();
+        boundaries
+        .iter()
+        .enumerate()
+        .map(|(i,start)|{
+            let end=boundaries
+            .get(i+1)
+            .map_or(char::MAX, |next| state::prev_char(*next).expect("boundaries are sorted"));
+            ClassRange::new(*start, end)
+        })
+        .filter(|range| sets.iter().any(|set| set.contains(range.start)))
         .collect()
     }
 
+    pub fn get_looks(&self)->BTreeSet<Look>{
+        Vec::from(self.clone())
+        .iter()
+        .flat_map(|state| state.adjacent())
+        .filter_map(|(symbol,_)|{
+            match symbol {
+                Symbol::Look(look)=>Some(look),
+                _=>None
+            }
+        })
+        .collect()
+    }
 }
 
 impl From<Automaton> for Vec<State> {
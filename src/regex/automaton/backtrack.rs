@@ -0,0 +1,588 @@
+use std::ops::Range;
+
+use crate::regex::ast::{Ast,Backreference,LookAroundKind};
+use crate::regex::unicode_tables;
+use super::{Class,Look};
+use super::state::LookBehind;
+
+
+/*
+Backtracking matcher for patterns automata can't express, like backreferences, look-arounds and atomic groups.
+The ast is compiled to a small program and run with an explicit stack of choices,
+so long haystacks don't grow the call stack. Choices are tried in the same priority order
+the Pike VM uses, and like the Pike VM a path reaching an instruction at a position some path
+already reached it at is dropped, which gives the same leftmost-first matches and groups and
+keeps the search linear. Without a backreference that's sound, the rest of the search can't depend
+on the path that got there, with one only the paths coming back to a loop they went through
+without consuming anything are dropped.
+Every instruction run costs a step and a search gives up once it used up the limit
+*/
+#[derive(Debug)]
+pub struct Backtracker{
+    program:Vec<Inst>,
+    slots:usize,
+    registers:usize,
+    memoize:bool,
+    limit:usize
+}
+
+/*the search ran out of steps before it could decide whether there is a match*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq)]
+pub struct LimitExceeded;
+
+#[derive(Clone,Debug)]
+enum Inst{
+    Char(char),
+    Class(Class),
+    Look(Look),
+    /*tries the first target and falls back to the second*/
+    Split(usize,usize),
+    Jump(usize),
+    Save(usize),
+    /*
+    fails when the path was at this loop state before without consuming anything since,
+    the way the Pike VM drops a thread reaching a state another one already reached at that position
+    */
+    Loop(usize),
+    Backreference(Backreference),
+    /*runs the body right after it as a search of its own and continues at next when the assertion holds*/
+    LookAhead{negated:bool,next:usize},
+    /*same for a body that starts min to max chars back and has to end at the current position*/
+    LookBehind{negated:bool,next:usize,min:usize,max:usize},
+    AtomicStart,
+    /*drops the choices made since the matching AtomicStart so the group is never backtracked into*/
+    AtomicEnd,
+    Match
+}
+
+/*
+work left to do when the current path fails, restores are undone on the way back to a choice
+*/
+enum Frame{
+    Choice{pc:usize,at:usize},
+    RestoreSlot{slot:usize,value:Option<usize>},
+    RestoreRegister{register:usize,value:usize},
+    /*where an atomic group started, choices above it are cut when the group ends*/
+    Atomic
+}
+
+impl Backtracker {
+    pub const DEFAULT_LIMIT:usize=10_000_000;
+
+    pub fn from_ast(ast:&Ast,limit:usize)->Backtracker{
+        let mut compiler=Compiler::default();
+        compiler.compile(ast);
+        compiler.program.push(Inst::Match);
+        let memoize=!compiler.program.iter().any(|inst| matches!(inst,Inst::Backreference(_)));
+        Backtracker{
+            program:compiler.program,
+            slots:compiler.slots.max(2),
+            registers:compiler.registers,
+            memoize,
+            limit
+        }
+    }
+
+    /*
+    Leftmost-first search, returns the byte range of the match
+    */
+    pub fn find(&self,haystack:&str)->Result<Option<Range<usize>>,LimitExceeded>{
+        let slots=self.captures(haystack)?;
+        Ok(slots.and_then(|slots| Some(slots[0]?..slots[1]?)))
+    }
+
+    /*
+    Tries every start position in order and returns the slots of the first match,
+    laid out like NFA::captures
+    */
+    pub fn captures(&self,haystack:&str)->Result<Option<Vec<Option<usize>>>,LimitExceeded>{
+        let mut search=Search{
+            program:&self.program,
+            haystack,
+            slots:vec![None;self.slots],
+            registers:vec![usize::MAX;self.registers],
+            visited:self.memoize.then(|| Visited::new(self.program.len(), haystack.len())),
+            stack:Vec::new(),
+            steps:0,
+            limit:self.limit
+        };
+
+        let starts=haystack.char_indices().map(|(at,_)| at).chain([haystack.len()]);
+        for start in starts{
+            if let Some(end)=search.run(0, start, None)? {
+                search.slots[0]=Some(start);
+                search.slots[1]=Some(end);
+                return Ok(Some(search.slots));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(Default)]
+struct Compiler{
+    program:Vec<Inst>,
+    slots:usize,
+    registers:usize
+}
+
+impl Compiler {
+    fn compile(&mut self,ast:&Ast){
+        match ast {
+            Ast::Empty=>{},
+            Ast::Literal(c)=>self.program.push(Inst::Char(*c)),
+            Ast::Class(class)=>self.program.push(Inst::Class(class.clone())),
+            Ast::Look(look)=>self.program.push(Inst::Look(*look)),
+            Ast::Concat(items)=>items.iter().for_each(|item| self.compile(item)),
+            Ast::Alternation(alternatives)=>{
+                /*each alternative but the last splits off the ones after it and jumps to the end*/
+                let mut jumps=Vec::new();
+                for (i,alternative) in alternatives.iter().enumerate(){
+                    let split=self.program.len();
+                    let last=i+1==alternatives.len();
+                    if !last {
+                        self.program.push(Inst::Split(split+1, 0));
+                    }
+                    self.compile(alternative);
+                    if !last {
+                        jumps.push(self.program.len());
+                        self.program.push(Inst::Jump(0));
+                        self.program[split]=Inst::Split(split+1, self.program.len());
+                    }
+                }
+                let end=self.program.len();
+                jumps.into_iter().for_each(|jump| self.program[jump]=Inst::Jump(end));
+            },
+            Ast::Repetition(repetition)=>{
+                let (min,max)=repetition.kind.bounds();
+                match max {
+                    None=>{
+                        /*x{n,} is n-1 copies and x+ like the automaton builds it*/
+                        for _ in 1..min{
+                            self.compile(&repetition.ast);
+                        }
+                        match min {
+                            0=>self.compile_star(&repetition.ast, repetition.greedy),
+                            _=>self.compile_plus(&repetition.ast, repetition.greedy)
+                        }
+                    }
+                    Some(max)=>{
+                        for _ in 0..min{
+                            self.compile(&repetition.ast);
+                        }
+                        /*x{n,m} leaves after any of the optional copies, like (x(x)?)?*/
+                        let mut splits=Vec::new();
+                        for _ in min..max{
+                            splits.push(self.program.len());
+                            self.program.push(Inst::Split(0, 0));
+                            self.compile(&repetition.ast);
+                        }
+                        let end=self.program.len();
+                        for split in splits{
+                            self.program[split]=Compiler::split(split+1, end, repetition.greedy);
+                        }
+                    }
+                }
+            },
+            Ast::Group(group)=>{
+                self.slots=self.slots.max(2*group.index+2);
+                self.program.push(Inst::Save(2*group.index));
+                self.compile(&group.ast);
+                self.program.push(Inst::Save(2*group.index+1));
+            },
+            Ast::Backreference(backreference)=>{
+                self.slots=self.slots.max(2*backreference.index+2);
+                self.program.push(Inst::Backreference(*backreference));
+            },
+            Ast::Atomic(ast)=>{
+                self.program.push(Inst::AtomicStart);
+                self.compile(ast);
+                self.program.push(Inst::AtomicEnd);
+            },
+            Ast::LookAround(look_around)=>{
+                let look=self.program.len();
+                self.program.push(Inst::Match);
+                self.compile(&look_around.ast);
+                self.program.push(Inst::Match);
+
+                let next=self.program.len();
+                let (min,max)=look_around.ast.length_bounds();
+                let max=max.unwrap_or(min).max(min);
+                self.program[look]=match look_around.kind {
+                    LookAroundKind::Ahead=>Inst::LookAhead{negated:false,next},
+                    LookAroundKind::NotAhead=>Inst::LookAhead{negated:true,next},
+                    LookAroundKind::Behind=>Inst::LookBehind{negated:false,next,min,max},
+                    LookAroundKind::NotBehind=>Inst::LookBehind{negated:true,next,min,max},
+                };
+            },
+        }
+    }
+
+    /*
+    L0: loop r; split L1, L2
+    L1: ast; jump L0
+    L2:
+    */
+    fn compile_star(&mut self,ast:&Ast,greedy:bool){
+        let start=self.program.len();
+        self.push_loop();
+        let split=self.program.len();
+        self.program.push(Inst::Split(0, 0));
+        self.compile(ast);
+        self.program.push(Inst::Jump(start));
+        let end=self.program.len();
+        self.program[split]=Compiler::split(split+1, end, greedy);
+    }
+
+    /*
+    L0: loop r1; ast
+        loop r2; split L0, L1
+    L1:
+    */
+    fn compile_plus(&mut self,ast:&Ast,greedy:bool){
+        let start=self.program.len();
+        self.push_loop();
+        self.compile(ast);
+        self.push_loop();
+        let split=self.program.len();
+        self.program.push(Compiler::split(start, split+1, greedy));
+    }
+
+    fn push_loop(&mut self){
+        self.program.push(Inst::Loop(self.registers));
+        self.registers+=1;
+    }
+
+    /*greedy repetitions prefer going into the ast, lazy ones prefer leaving*/
+    fn split(repeat:usize,leave:usize,greedy:bool)->Inst{
+        if greedy {
+            Inst::Split(repeat, leave)
+        }
+        else {
+            Inst::Split(leave, repeat)
+        }
+    }
+}
+
+struct Search<'p,'h>{
+    program:&'p [Inst],
+    haystack:&'h str,
+    slots:Vec<Option<usize>>,
+    registers:Vec<usize>,
+    /*instructions and positions some path reached, look-arounds run without it*/
+    visited:Option<Visited>,
+    stack:Vec<Frame>,
+    steps:usize,
+    limit:usize
+}
+
+/*one bit for every instruction at every position, the pages only get allocated once a bit is set*/
+struct Visited{
+    bits:Vec<u64>,
+    program_len:usize
+}
+
+impl Visited {
+    fn new(program_len:usize,haystack_len:usize)->Visited{
+        Visited{
+            bits:vec![0;(program_len*(haystack_len+1)).div_ceil(64)],
+            program_len
+        }
+    }
+
+    /*false when pc was already reached at at*/
+    fn insert(&mut self,pc:usize,at:usize)->bool{
+        let bit=at*self.program_len+pc;
+        let (word,mask)=(bit/64,1<<(bit%64));
+        let new=self.bits[word]&mask==0;
+        self.bits[word]|=mask;
+        new
+    }
+}
+
+impl Search<'_,'_> {
+    /*
+    end of the first match of the program at pc starting at start, restores slots and registers when there is none.
+    With end set only matches ending there count.
+    Frames below the ones this run pushes belong to an enclosing run
+    */
+    fn run(&mut self,pc:usize,start:usize,end:Option<usize>)->Result<Option<usize>,LimitExceeded>{
+        let program=self.program;
+        let base=self.stack.len();
+        self.stack.push(Frame::Choice{pc,at:start});
+
+        while self.stack.len()>base {
+            let frame=self.stack.pop().expect("checked the stack is above base");
+            let (mut pc,mut at)=match frame {
+                Frame::Choice{pc,at}=>(pc,at),
+                Frame::RestoreSlot{slot,value}=>{
+                    self.slots[slot]=value;
+                    continue;
+                }
+                Frame::RestoreRegister{register,value}=>{
+                    self.registers[register]=value;
+                    continue;
+                }
+                Frame::Atomic=>continue
+            };
+
+            loop {
+                self.steps+=1;
+                if self.steps>self.limit {
+                    return Err(LimitExceeded);
+                }
+                if self.visited.as_mut().is_some_and(|visited| !visited.insert(pc, at)) {
+                    break;
+                }
+
+                let next=self.haystack[at..].chars().next();
+                match &program[pc] {
+                    Inst::Char(c) if next==Some(*c)=>{
+                        at+=c.len_utf8();
+                    }
+                    Inst::Class(class) if next.is_some_and(|c| class.contains(&c))=>{
+                        at+=next.map_or(0, char::len_utf8);
+                    }
+                    Inst::Look(look) if look.matches(LookBehind::new(self.haystack[..at].chars().next_back()), next)=>{},
+                    &Inst::Split(first,second)=>{
+                        self.stack.push(Frame::Choice{pc:second,at});
+                        pc=first;
+                        continue;
+                    }
+                    &Inst::Jump(to)=>{
+                        pc=to;
+                        continue;
+                    }
+                    Inst::Save(slot)=>{
+                        self.stack.push(Frame::RestoreSlot{slot:*slot,value:self.slots[*slot]});
+                        self.slots[*slot]=Some(at);
+                    }
+                    Inst::Loop(register) if self.registers[*register]!=at=>{
+                        self.stack.push(Frame::RestoreRegister{register:*register,value:self.registers[*register]});
+                        self.registers[*register]=at;
+                    }
+                    Inst::Backreference(backreference)=>{
+                        match self.match_backreference(backreference, at) {
+                            Some(end)=>at=end,
+                            None=>break
+                        }
+                    }
+                    &Inst::LookAhead{negated,next}=>{
+                        if !self.look_around(pc+1, [at], None, negated)? {
+                            break;
+                        }
+                        pc=next;
+                        continue;
+                    }
+                    &Inst::LookBehind{negated,next,min,max}=>{
+                        let starts=[at].into_iter().chain(self.haystack[..at].char_indices().rev().map(|(i,_)| i));
+                        let starts:Vec<usize>=starts.skip(min).take(max-min+1).collect();
+                        if !self.look_around(pc+1, starts, Some(at), negated)? {
+                            break;
+                        }
+                        pc=next;
+                        continue;
+                    }
+                    Inst::AtomicStart=>{
+                        self.stack.push(Frame::Atomic);
+                    }
+                    Inst::AtomicEnd=>{
+                        /*restores stay so backtracking past the group still undoes what it did*/
+                        let start=self.stack.iter().rposition(|frame| matches!(frame,Frame::Atomic)).expect("atomic groups end after they start");
+                        let restores:Vec<Frame>=self.stack.drain(start..).filter(|frame| !matches!(frame,Frame::Choice{..}|Frame::Atomic)).collect();
+                        self.stack.extend(restores);
+                    }
+                    Inst::Match if end.is_none_or(|end| end==at)=>{
+                        self.stack.truncate(base);
+                        return Ok(Some(at));
+                    }
+                    _=>break
+                }
+                pc+=1;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /*
+    whether the body at pc matches from one of the starts, groups a positive look-around captured
+    stay set until the enclosing run backtracks past it
+    */
+    fn look_around(&mut self,pc:usize,starts:impl IntoIterator<Item=usize>,end:Option<usize>,negated:bool)->Result<bool,LimitExceeded>{
+        let outer_slots=self.slots.clone();
+        let visited=self.visited.take();
+        let mut matched=false;
+        for start in starts{
+            if self.run(pc, start, end)?.is_some() {
+                matched=true;
+                break;
+            }
+        }
+        self.visited=visited;
+
+        if matched&&!negated {
+            for (slot,value) in outer_slots.into_iter().enumerate(){
+                if self.slots[slot]!=value {
+                    self.stack.push(Frame::RestoreSlot{slot,value});
+                }
+            }
+        }
+        else {
+            self.slots=outer_slots;
+        }
+
+        Ok(matched!=negated)
+    }
+
+    /*end of the text the group captured when it follows at, None while the group is unset*/
+    fn match_backreference(&self,backreference:&Backreference,at:usize)->Option<usize>{
+        let start=self.slots[2*backreference.index]?;
+        let end=self.slots[2*backreference.index+1]?;
+        let mut rest=self.haystack[at..].chars();
+        let mut matched=at;
+
+        for c in self.haystack[start..end].chars(){
+            let next=rest.next()?;
+            let equal=c==next||(backreference.case_insensitive&&unicode_tables::case_folds_between(c, c).iter().any(|(_,folds)| folds.contains(&next)));
+            if !equal {
+                return None;
+            }
+            matched+=next.len_utf8();
+        }
+
+        Some(matched)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regex::automaton::NFA;
+    use crate::regex::transform::parse;
+
+    fn find(re:&str,haystack:&str)->Option<Range<usize>>{
+        Backtracker::from_ast(&parse(re).unwrap(), Backtracker::DEFAULT_LIMIT).find(haystack).unwrap()
+    }
+
+    #[test]
+    fn find_same_as_nfa() {
+        for (re,haystack) in [
+            ("a+?b|a","xaaab"),
+            ("(a|ab)(c|bcd)(d*)","abcd"),
+            ("<.*?>","<a><b>"),
+            ("(a*)*b","aab"),
+            ("(a|)+b","aab"),
+            ("(?:a?|b)*","b"),
+            ("(a?|b)*","bb"),
+            ("(a?|b)+","bb"),
+            ("(?:^|\\w)*","ab"),
+            ("(a*)*","b"),
+            ("(?:x*?)+","xxx"),
+            ("(?:x*?)+y","xxy"),
+            ("(?:(x)*?)+","xxx"),
+            ("(?:(x)?)+","xx"),
+            ("(?:()?)+","x"),
+            ("b(?:()?)+","b"),
+            ("(?:x*?){2,}","xxx"),
+            ("(?:(x?|y)(x?|y))*","xyx"),
+            ("(?:(?:([ab])*?)+)+","ba"),
+            ("x{2,3}?y|x","xxxxy"),
+            ("^\\w+$|\\bc","ab c"),
+            ("","abc"),
+        ]{
+            let ast=parse(re).unwrap();
+            let nfa=NFA::from_ast(&ast).captures(haystack);
+            let backtracker=Backtracker::from_ast(&ast, Backtracker::DEFAULT_LIMIT).captures(haystack).unwrap();
+            /*the Pike VM leaves out the slots after the last group it saved*/
+            let nfa=nfa.map(|mut slots|{slots.resize(backtracker.as_ref().map_or(0, Vec::len), None); slots});
+            assert_eq!(backtracker,nfa,"{re}");
+        }
+    }
+
+    #[test]
+    fn find_backreference() {
+        assert_eq!(find("\\b(\\w+)\\s+\\1\\b","this is is fine"),Some(5..10));
+        assert_eq!(find("(a)|\\1b","b"),None);
+        assert_eq!(find("(?<q>['\"]).*?\\k<q>","say \"it's\" ok"),Some(4..10));
+        assert_eq!(find("(?i)(ab)\\1","xAbaB"),Some(1..5));
+        assert_eq!(find("(a*)\\1$","aaaa"),Some(0..4));
+        assert_eq!(find("(a*)\\1$","aaa"),Some(1..3));
+    }
+
+    #[test]
+    fn find_look_around() {
+        assert_eq!(find("\\w+(?=,)","ab cd, ef"),Some(3..5));
+        assert_eq!(find("\\b\\w+\\b(?!,)","ab, cd"),Some(4..6));
+        assert_eq!(find("(?<=\\$)\\d+","12 $34"),Some(4..6));
+        assert_eq!(find("(?<!-)\\b\\d+","-5 7"),Some(3..4));
+        assert_eq!(find("(?<=ab|c)d","cd abd"),Some(1..2));
+        assert_eq!(find("(?<=^|é)x","éx"),Some(2..3));
+        assert_eq!(find("^(?=.*\\d)(?=.*[a-z]).{4,}$","abc1"),Some(0..4));
+        assert_eq!(find("^(?=.*\\d)(?=.*[a-z]).{4,}$","abcd"),None);
+        assert_eq!(find("(?!a)","aab"),Some(2..2));
+    }
+
+    #[test]
+    fn look_around_captures() {
+        let captures=|re:&str,haystack:&str| Backtracker::from_ast(&parse(re).unwrap(), Backtracker::DEFAULT_LIMIT).captures(haystack).unwrap();
+
+        assert_eq!(captures("(?=(\\w+))\\w","ab"),Some(vec![Some(0),Some(1),Some(0),Some(2)]));
+        assert_eq!(captures("(?!(a))\\w","ab"),Some(vec![Some(1),Some(2),None,None]));
+        assert_eq!(captures("(?=(a))ab|a(c)","ac"),Some(vec![Some(0),Some(2),None,None,Some(1),Some(2)]));
+    }
+
+    #[test]
+    fn find_atomic() {
+        assert_eq!(find("(?>a*)a","aaa"),None);
+        assert_eq!(find("a*+a","aaa"),None);
+        assert_eq!(find("a?+a","a"),None);
+        assert_eq!(find("(?>a|ab)c","abc"),None);
+        assert_eq!(find("(?>ab|a)c","abc"),Some(0..3));
+        assert_eq!(find("x++y|x","xxy"),Some(0..3));
+        assert_eq!(find("\"(?:[^\"\\\\]++|\\\\.)*+\"","say \"a\\\"b\" ok"),Some(4..10));
+        assert_eq!(find("a{2,3}+a","aaaa"),Some(0..4));
+        assert_eq!(find("a{2,3}+a","aaa"),None);
+
+        let captures=Backtracker::from_ast(&parse("(?>(a))b|ac").unwrap(), Backtracker::DEFAULT_LIMIT).captures("ac").unwrap();
+        assert_eq!(captures,Some(vec![Some(0),Some(2),None,None]));
+    }
+
+    /*
+    nested quantifiers take exponential time to fail once a backreference turns off dropping
+    positions already reached, without one and in the possessive and atomic forms they
+    fail within a step budget proportional to the input
+    */
+    #[test]
+    fn linear_on_catastrophic_inputs() {
+        let find_within=|re:&str,haystack:&str,limit:usize| Backtracker::from_ast(&parse(re).unwrap(), limit).find(haystack);
+
+        let short=format!("{}b","a".repeat(30));
+        assert_eq!(find_within("^(a+)+\\1$", &short, 1_000_000),Err(LimitExceeded));
+        assert_eq!(find_within("^(\\w+\\s?)*\\1$", &format!("{}!","ab ".repeat(15)), 1_000_000),Err(LimitExceeded));
+
+        for n in [1_000,10_000,100_000]{
+            let haystack=format!("{}b","a".repeat(n));
+            assert_eq!(find_within("^(a+)+$", &haystack, 20*n),Ok(None));
+            assert_eq!(find_within("^(a++)+$", &haystack, 20*n),Ok(None));
+            assert_eq!(find_within("^(?>a+)+$", &haystack, 20*n),Ok(None));
+
+            let words=format!("{}!","ab ".repeat(n/3));
+            assert_eq!(find_within("^(\\w+\\s?)*$", &words, 20*n),Ok(None));
+            assert_eq!(find_within("^(\\w++\\s?+)*+$", &words, 20*n),Ok(None));
+            assert_eq!(find_within("^(?>(?>\\w+)\\s?)*$", &words, 20*n),Ok(None));
+        }
+    }
+
+    #[test]
+    fn step_limit() {
+        let ast=parse("(a+)+\\1b").unwrap();
+        let haystack="a".repeat(30);
+        assert_eq!(Backtracker::from_ast(&ast, 100_000).find(&haystack),Err(LimitExceeded));
+
+        let haystack="a".repeat(100_000);
+        assert_eq!(Backtracker::from_ast(&parse("(a)\\1*$").unwrap(), Backtracker::DEFAULT_LIMIT).find(&haystack),Ok(Some(0..100_000)));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -7,90 +8,168 @@ use super::Automaton;
 use super::State;
 use super::NFA;
 use super::state::StateRef;
-use super::state::Symbol;
+use super::state::{Symbol,Class,ClassRange,ClassSet,Look,LookBehind};
+use crate::regex::Error;
 
+/*
+A DFA state is the set of NFA states it simulates together with what its looks
+can still ask about the char before it
+*/
+type DFAKey=(BTreeSet<usize>,LookBehind);
 
 #[derive(Debug)]
 pub struct DFA{
-    automaton:Automaton
+    /*the pattern behind a lazy loop over every char, match_input searches with it*/
+    unanchored:Automaton
 }
 
 impl PartialEq for DFA {
     fn eq(&self, other: &Self) -> bool {
-        self.automaton == other.automaton
+        self.unanchored == other.unanchored
     }
 }
 
 impl DFA {
-    pub fn construct_dfa(re :&String)->Result<DFA,String>{
+    pub fn construct_dfa(re :&str)->Result<DFA,Error>{
         let nfa=NFA::construct_nfa(re)?;
-        let nfa_success_id=nfa.automaton.end_state.borrow().get_id();
-        let alphabet=nfa.automaton.get_alphabet();
+        Ok(DFA::from_nfa(&nfa))
+    }
+
+    pub fn from_nfa(nfa:&NFA)->DFA{
+        let any=Automaton::automaton_transition(&Symbol::CharClass(Class::Set(ClassSet::full())));
+        let any=Automaton::automaton_zero_or_more(&any, false);
+        let unanchored=Automaton::automaton_concat(&any, &nfa.automaton);
+
+        DFA{
+            unanchored:DFA::subset_construction(&unanchored)
+        }
+    }
+
+    /*
+    Accepting states get an epsilon edge to the end state. States that only accept when the
+    input ends there get a Look::End edge instead, and a match that a look only allows before
+    some char c is carried into the state reached on c
+    */
+    fn subset_construction(nfa:&Automaton)->Automaton{
+        let nfa_end=nfa.end_state.borrow().clone();
+        let alphabet=nfa.get_alphabet();
+        let looks=nfa.get_looks();
 
-        let start_state_set=nfa.automaton.start_state.borrow().follow_epsilon();
+        let start_state_set=nfa.start_state.borrow().follow_epsilon();
+        let start_behind=LookBehind::new(None).restrict(&looks);
         let start=State::new_accept_ref();
         let end=State::new_accept_ref();
         let mut success_dfa_states=Vec::new();
-        let mut visited_dfa:HashMap<BTreeSet<usize>,StateRef>=
-        [(start_state_set.iter().map(|state| state.get_id()).collect(), start.clone())].into_iter().collect();
+        let mut end_of_input_dfa_states=Vec::new();
+        let mut visited_dfa:HashMap<DFAKey,StateRef>=
+        [((DFA::ids(&start_state_set),start_behind), start.clone())].into_iter().collect();
 
-        let mut queue=VecDeque::from([(start.clone(),start_state_set)]);
+        let mut queue=VecDeque::from([(start.clone(),start_state_set,start_behind)]);
 
-        while !queue.is_empty() {
-            let (node_ref,nfa_states)=queue.pop_front().expect("checked for empty");
-            if nfa_states.iter().any(|state| state.get_id()==nfa_success_id){
+        while let Some((node_ref,nfa_states,behind))=queue.pop_front() {
+            if nfa_states.iter().any(|state| state.get_id()==nfa_end.get_id()){
                 success_dfa_states.push(node_ref.clone());
             }
-            queue.append(&mut DFA::generate_transitions(node_ref.clone(), &nfa_states, &alphabet, &mut visited_dfa));
+            else if DFA::look_closure(&nfa_states, behind, None).iter().any(|state| state.get_id()==nfa_end.get_id()){
+                end_of_input_dfa_states.push(node_ref.clone());
+            }
+            queue.append(&mut DFA::generate_transitions(node_ref.clone(), &nfa_states, behind, &nfa_end, &alphabet, &looks, &mut visited_dfa));
         }
 
         for success_state in success_dfa_states{
             success_state.borrow_mut().insert_transition_ord(Symbol::Epsilon, &end)
         }
+        for end_of_input_state in end_of_input_dfa_states{
+            end_of_input_state.borrow_mut().insert_transition_ord(Symbol::Look(Look::End), &end)
+        }
 
-        Ok(DFA{
-            automaton:Automaton{
-                start_state:start,
-                end_state:end
-            }
-        })
+        Automaton{
+            start_state:start,
+            end_state:end
+        }
     }
 
-    /*Before adding character class temporary measure to make regex match part of string */
+    /*true when the pattern matches anywhere in input*/
     pub fn match_input(&self,input:&str)->bool{
-        let mut cur_state=self.automaton.start_state.borrow().clone();
+        let mut cur_state=self.unanchored.start_state.borrow().clone();
         for c in input.chars(){
+            if DFA::is_accepting(&cur_state) {
+                return true;
+            }
             cur_state=match cur_state.delta(Symbol::Alphabet(c)).into_iter().next() {
                 Some(next_state) => next_state,
-                None => continue,
+                None => return false,
             };
-            if cur_state.follow_epsilon().iter().any(|final_state| matches!(final_state,State::Accept(_))) {
-                return true;
-            }
         }
-        false
+        DFA::is_accepting(&cur_state)||!cur_state.delta(Symbol::Look(Look::End)).is_empty()
+    }
+
+    fn is_accepting(state:&State)->bool{
+        !state.delta(Symbol::Epsilon).is_empty()
+    }
+
+    fn ids(nfa_states:&[State])->BTreeSet<usize>{
+        nfa_states.iter().map(|state| state.get_id()).collect()
     }
 
-    fn generate_transitions(dfa_state:StateRef,nfa_states:&Vec<State>,alphabet:&HashSet<char>,visited_dfa:&mut HashMap<BTreeSet<usize>,StateRef>)
-    ->VecDeque<(StateRef,Vec<State>)>{
+    fn generate_transitions(dfa_state:StateRef,nfa_states:&[State],behind:LookBehind,nfa_end:&State,alphabet:&Vec<ClassRange>,
+        looks:&BTreeSet<Look>,visited_dfa:&mut HashMap<DFAKey,StateRef>)
+    ->VecDeque<(StateRef,Vec<State>,LookBehind)>{
         let mut dfa_nfa_pairs=VecDeque::new();
-        for c in alphabet{
-            let delta_states=DFA::delta_states(*c, nfa_states);
-            let new_ids=delta_states.iter().map(|state|state.get_id()).collect();
+        let accepting=nfa_states.iter().any(|state| state.get_id()==nfa_end.get_id());
+        /*ranges leading to the same nfa states share one transition*/
+        let mut targets:BTreeMap<DFAKey,(Vec<State>,ClassSet)>=BTreeMap::new();
+        for range in alphabet{
+            let current_states=DFA::look_closure(nfa_states, behind, Some(range.start));
+            let mut delta_states=DFA::delta_states(range.start, &current_states);
+            if !accepting&&current_states.iter().any(|state| state.get_id()==nfa_end.get_id()){
+                delta_states.push(nfa_end.clone());
+            }
             if !delta_states.is_empty(){
-                let target_dfa_state=visited_dfa.entry(new_ids).or_insert_with(||{
-                    let new_node=State::new_accept_ref();
-                    dfa_nfa_pairs.push_back((new_node.clone(),delta_states));
-                    new_node
-                });
-                
-                dfa_state.borrow_mut().insert_transition_ord(Symbol::Alphabet(*c), &target_dfa_state);
+                let new_behind=LookBehind::new(Some(range.start)).restrict(looks);
+                targets
+                .entry((DFA::ids(&delta_states),new_behind))
+                .or_insert_with(||(delta_states,ClassSet::empty()))
+                .1
+                .push(*range);
             }
         }
+
+        for (new_key,(delta_states,set)) in targets{
+            let new_behind=new_key.1;
+            let target_dfa_state=visited_dfa.entry(new_key).or_insert_with(||{
+                let new_node=State::new_accept_ref();
+                dfa_nfa_pairs.push_back((new_node.clone(),delta_states,new_behind));
+                new_node
+            });
+            
+            dfa_state.borrow_mut().insert_transition_ord(Symbol::from_set(set), target_dfa_state);
+        }
         
         dfa_nfa_pairs
     }
 
+    /*nfa_states plus every state reached over looks that hold between behind and next*/
+    fn look_closure(nfa_states:&[State],behind:LookBehind,next:Option<char>)->Vec<State>{
+        let mut closure=nfa_states.to_vec();
+        let mut visited:HashSet<usize>=closure.iter().map(|state| state.get_id()).collect();
+        let mut i=0;
+        while let Some(state)=closure.get(i).cloned() {
+            i+=1;
+            for (symbol,to) in state.adjacent(){
+                let Some(to)=to.filter(|_| matches!(symbol,Symbol::Look(look) if look.matches(behind, next))) else {
+                    continue;
+                };
+                for reached in to.borrow().follow_epsilon(){
+                    if visited.insert(reached.get_id()) {
+                        closure.push(reached);
+                    }
+                }
+            }
+        }
+        closure
+    }
+
     fn delta_states(c:char,nfa_states:&Vec<State>)->Vec<State>{
         let mut visited_nfa:HashMap<usize,State>=HashMap::new();
         for nfa_state in nfa_states{
@@ -112,23 +191,22 @@ impl DFA {
 
 
 #[cfg(test)]
-mod dfa{
+mod tests{
     use super::*;
+    use super::super::state::Class;
 
     #[test]
     fn concat_1(){
         let re="abc";
-        let dfa=DFA::construct_dfa(&re.to_string()).expect("Expect successful dfa construction");
+        let dfa=DFA::subset_construction(&NFA::construct_nfa(re).expect("Expect successful nfa construction").automaton);
         let accept=State::new_accept_ref();
         let to_accept=State::new_transition_ref(Symbol::Epsilon, Some(accept.clone()));
         let c=State::new_transition_ref(Symbol::Alphabet('c'), Some(to_accept));
         let b=State::new_transition_ref(Symbol::Alphabet('b'), Some(c));
         let a=State::new_transition_ref(Symbol::Alphabet('a'), Some(b));
-        let expect=DFA{
-            automaton:Automaton{
-                start_state:a,
-                end_state:accept
-            }
+        let expect=Automaton{
+            start_state:a,
+            end_state:accept
         };
         
         assert_eq!(dfa,expect)
@@ -137,17 +215,15 @@ mod dfa{
     #[test]
     fn or_1(){
         let re="ab|c";
-        let dfa=DFA::construct_dfa(&re.to_string()).expect("Expect successful dfa construction");
+        let dfa=DFA::subset_construction(&NFA::construct_nfa(re).expect("Expect successful nfa construction").automaton);
         let accept=State::new_accept_ref();
         let to_accept=State::new_transition_ref(Symbol::Epsilon, Some(accept.clone()));
-        let bc=State::new_transition_ref(Symbol::Alphabet('c'), Some(to_accept.clone()));
-        bc.borrow_mut().insert_transition_ord(Symbol::Alphabet('b'), &to_accept);
-        let a=State::new_transition_ref(Symbol::Alphabet('a'), Some(bc));
-        let expect=DFA{
-            automaton:Automaton{
-                start_state:a,
-                end_state:accept
-            }
+        let b=State::new_transition_ref(Symbol::Alphabet('b'), Some(to_accept.clone()));
+        let a=State::new_transition_ref(Symbol::Alphabet('a'), Some(b));
+        a.borrow_mut().insert_transition_ord(Symbol::Alphabet('c'), &to_accept);
+        let expect=Automaton{
+            start_state:a,
+            end_state:accept
         };
         
         assert_eq!(dfa,expect)
@@ -156,17 +232,15 @@ mod dfa{
     #[test]
     fn asterisk_1(){
         let re="ab*c";
-        let dfa=DFA::construct_dfa(&re.to_string()).expect("Expect successful dfa construction");
+        let dfa=DFA::subset_construction(&NFA::construct_nfa(re).expect("Expect successful nfa construction").automaton);
         let accept=State::new_accept_ref();
         let to_accept=State::new_transition_ref(Symbol::Epsilon, Some(accept.clone()));
         let bs_c=State::new_transition_ref(Symbol::Alphabet('c'), Some(to_accept.clone()));
         bs_c.borrow_mut().insert_transition_ord(Symbol::Alphabet('b'), &bs_c);
         let a=State::new_transition_ref(Symbol::Alphabet('a'), Some(bs_c));
-        let expect=DFA{
-            automaton:Automaton{
-                start_state:a,
-                end_state:accept
-            }
+        let expect=Automaton{
+            start_state:a,
+            end_state:accept
         };
         
         assert_eq!(dfa,expect)
@@ -176,21 +250,56 @@ mod dfa{
     #[test]
     fn all_1(){
         let re="(ab.)*c";
-        let dfa=DFA::construct_dfa(&re.to_string()).expect("Expect successful dfa construction");
+        let dfa=DFA::subset_construction(&NFA::construct_nfa(re).expect("Expect successful nfa construction").automaton);
         let accept=State::new_accept_ref();
         let to_accept=State::new_transition_ref(Symbol::Epsilon, Some(accept.clone()));
-        let all=State::new_transition_ref(Symbol::Alphabet('c'), Some(to_accept.clone()));
-        all.borrow_mut().insert_transition_ord(Symbol::Alphabet('a'), &all);
+        let a=State::new_transition_ref(Symbol::Alphabet('c'), Some(to_accept.clone()));
+        let mut all_except_newline=ClassSet::from_char('\n');
+        all_except_newline.negate();
+        let all=State::new_transition_ref(Symbol::CharClass(Class::Set(all_except_newline)), Some(a.clone()));
         let b=State::new_transition_ref(Symbol::Alphabet('b'), Some(all));
-        let a=State::new_transition_ref(Symbol::Alphabet('a'), Some(b));
-        a.borrow_mut().insert_transition(Symbol::new_alphabet('c'), &to_accept);
-        let expect=DFA{
-            automaton:Automaton{
-                start_state:a,
-                end_state:accept
-            }
+        a.borrow_mut().insert_transition_ord(Symbol::new_alphabet('a'), &b);
+        let expect=Automaton{
+            start_state:a,
+            end_state:accept
+        };
+        
+        assert_eq!(dfa,expect)
+    }
+
+    #[test]
+    fn class_1(){
+        let re="[a-c]x|bz";
+        let dfa=DFA::subset_construction(&NFA::construct_nfa(re).expect("Expect successful nfa construction").automaton);
+        let accept=State::new_accept_ref();
+        let to_accept=State::new_transition_ref(Symbol::Epsilon, Some(accept.clone()));
+        let x=State::new_transition_ref(Symbol::Alphabet('x'), Some(to_accept.clone()));
+        let x_or_z=ClassSet::new([ClassRange::new('x','x'),ClassRange::new('z','z')]);
+        let xz=State::new_transition_ref(Symbol::CharClass(Class::Set(x_or_z)), Some(to_accept.clone()));
+        let a=State::new_transition_ref(Symbol::Alphabet('b'), Some(xz));
+        let a_or_c=ClassSet::new([ClassRange::new('a','a'),ClassRange::new('c','c')]);
+        a.borrow_mut().insert_transition_ord(Symbol::CharClass(Class::Set(a_or_c)), &x);
+        let expect=Automaton{
+            start_state:a,
+            end_state:accept
+        };
+        
+        assert_eq!(dfa,expect)
+    }
+
+    #[test]
+    fn anchor_1(){
+        let re="ab*\\z";
+        let dfa=DFA::subset_construction(&NFA::construct_nfa(re).expect("Expect successful nfa construction").automaton);
+        let accept=State::new_accept_ref();
+        let to_accept=State::new_transition_ref(Symbol::Look(Look::End), Some(accept.clone()));
+        to_accept.borrow_mut().insert_transition_ord(Symbol::Alphabet('b'), &to_accept.clone());
+        let a=State::new_transition_ref(Symbol::Alphabet('a'), Some(to_accept));
+        let expect=Automaton{
+            start_state:a,
+            end_state:accept
         };
         
         assert_eq!(dfa,expect)
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,14 @@
-use crate::regex::transform::{ThompsonOp,to_thompson_postfix};
+use std::collections::HashSet;
+use std::ops::Range;
+
+use crate::regex::ast::{Ast,RepetitionKind};
+use crate::regex::simplify::simplify;
+use crate::regex::transform::parse;
+use crate::regex::{Error,ErrorKind};
 use super::Automaton;
+use super::State;
 use super::Symbol;
+use super::state::LookBehind;
 
 
 #[derive(Debug)]
@@ -15,50 +23,206 @@ impl PartialEq for NFA {
 }
 
 impl NFA {
-    pub fn construct_nfa(re :&String)->Result<NFA,String>{
-        let thompson=to_thompson_postfix(re)?;
-        let mut stack:Vec<Automaton>=Vec::new();
-        println!("Thompson construction stack: {:?}",thompson);
-    
-        for op in &thompson{
-            match op {
-                ThompsonOp::Primary(c)=>{
-                    stack.push(Automaton::automaton_transition(&Symbol::new_alphabet(*c)));
-                },
-                ThompsonOp::Asterisk => {
-                    let automaton=stack.pop().unwrap();
-                    stack.push(Automaton::automaton_zero_or_one(&automaton));
-                },
-                ThompsonOp::Or => {
-                    let automaton2=stack.pop().unwrap();
-                    let automaton1=stack.pop().unwrap();
-                    stack.push(Automaton::automaton_alternate(&automaton1, &automaton2));
-                },
-                ThompsonOp::Concat => {
-                    let automaton2=stack.pop().unwrap();
-                    let automaton1=stack.pop().unwrap();
-                    stack.push(Automaton::automaton_concat(&automaton1, &automaton2));
-                },
-                ThompsonOp::All=>{
-                    stack.push(Automaton::automaton_transition(&Symbol::new_all_char()));
+    pub fn construct_nfa(re :&str)->Result<NFA,Error>{
+        let ast=parse(re)?;
+        if ast.needs_backtracking() {
+            return Err(Error::new(ErrorKind::NeedsBacktracking, re, 0..re.len()));
+        }
+        Ok(NFA::from_ast(&simplify(ast)))
+    }
+
+    /*panics on an ast that needs backtracking, check Ast::needs_backtracking first*/
+    pub fn from_ast(ast:&Ast)->NFA{
+        NFA{automaton:NFA::compile(ast)}
+    }
+
+    pub fn state_count(&self)->usize{
+        Vec::from(self.automaton.clone()).len()
+    }
+
+    /*
+    Leftmost-first search, returns the byte range of the match
+    */
+    pub fn find(&self,haystack:&str)->Option<Range<usize>>{
+        let slots=self.captures(haystack)?;
+        Some(slots[0]?..slots[1]?)
+    }
+
+    /*
+    Leftmost-longest search as POSIX wants it, returns the byte range of the match
+    */
+    pub fn find_longest(&self,haystack:&str)->Option<Range<usize>>{
+        let slots=self.captures_longest(haystack)?;
+        Some(slots[0]?..slots[1]?)
+    }
+
+    /*
+    Leftmost-first search running every NFA state at once (Pike VM).
+    Threads are kept in priority order, the first edge of a split comes before the second,
+    and once a thread accepts every thread behind it is dropped.
+    Returns the slots of the match, group i starts at slot 2*i and ends at slot 2*i+1,
+    trailing groups that never matched may be missing
+    */
+    pub fn captures(&self,haystack:&str)->Option<Vec<Option<usize>>>{
+        self.search(haystack, false)
+    }
+
+    /*
+    Leftmost-longest search, the match starts as early and then ends as late as possible.
+    Groups get the spans of the preferred path to that end, not the POSIX subexpression rules
+    */
+    pub fn captures_longest(&self,haystack:&str)->Option<Vec<Option<usize>>>{
+        self.search(haystack, true)
+    }
+
+    /*
+    Threads that started earlier come first since new threads are only added at the back,
+    so when longest is set an accepting thread only drops the threads that started after it
+    and the search goes on while threads that started no later are alive
+    */
+    fn search(&self,haystack:&str,longest:bool)->Option<Vec<Option<usize>>>{
+        let end_id=self.automaton.end_state.borrow().get_id();
+        let positions:Vec<(usize,Option<char>)>=haystack.char_indices().map(|(at,c)|(at,Some(c))).chain([(haystack.len(),None)]).collect();
+        let mut threads=Threads::default();
+        let mut matched=None;
+
+        for (i,&(at,c)) in positions.iter().enumerate(){
+            if matched.is_none(){
+                let behind=LookBehind::new(i.checked_sub(1).and_then(|prev| positions[prev].1));
+                threads.add(self.automaton.start_state.borrow().clone(), vec![Some(at),None], at, behind, c);
+            }
+            let next_behind=LookBehind::new(c);
+            let (next_at,next_c)=positions.get(i+1).copied().unwrap_or((at,None));
+
+            let mut next=Threads::default();
+            for (state,mut slots) in threads.list{
+                let start=slots[0];
+                if longest&&matched.as_ref().is_some_and(|matched:&Vec<Option<usize>>| matched[0]<start) {
+                    continue;
+                }
+                if state.get_id()==end_id {
+                    slots[1]=Some(at);
+                    matched=Some(slots);
+                    if longest {
+                        continue;
+                    }
+                    break;
+                }
+                let Some(c)=c else {
+                    continue;
+                };
+                for (symbol,to) in state.adjacent(){
+                    if let Some(to)=to.filter(|_| Symbol::Alphabet(c).contains(&symbol)) {
+                        next.add(to.borrow().clone(), slots.clone(), next_at, next_behind, next_c);
+                    }
                 }
-                _=>{panic!("Unhandled thomson op")}
+            }
+            threads=next;
+
+            if matched.is_some()&&threads.list.is_empty() {
+                break;
             }
         }
-    
-        if stack.len()!=1{
-            panic!("Expect there to be only 1 automaton for each regex");
+
+        matched
+    }
+
+    fn compile(ast:&Ast)->Automaton{
+        match ast {
+            Ast::Empty=>Automaton::automaton_empty(),
+            Ast::Literal(c)=>Automaton::automaton_transition(&Symbol::new_alphabet(*c)),
+            Ast::Class(class)=>Automaton::automaton_transition(&Symbol::CharClass(class.clone())),
+            Ast::Look(look)=>Automaton::automaton_transition(&Symbol::Look(*look)),
+            Ast::Concat(items)=>{
+                items
+                .iter()
+                .map(NFA::compile)
+                .reduce(|automaton1,automaton2| Automaton::automaton_concat(&automaton1, &automaton2))
+                .unwrap_or_else(Automaton::automaton_empty)
+            },
+            Ast::Alternation(alternatives)=>{
+                /*right fold keeps earlier alternatives on the preferred edge of each split*/
+                alternatives
+                .iter()
+                .rev()
+                .map(NFA::compile)
+                .reduce(|automaton2,automaton1| Automaton::automaton_alternate(&automaton1, &automaton2))
+                .unwrap_or_else(Automaton::automaton_empty)
+            },
+            Ast::Repetition(repetition)=>{
+                let automaton=||NFA::compile(&repetition.ast);
+                let greedy=repetition.greedy;
+                match repetition.kind {
+                    RepetitionKind::ZeroOrMore=>Automaton::automaton_zero_or_more(&automaton(),greedy),
+                    RepetitionKind::OneOrMore=>Automaton::automaton_one_or_more(&automaton(),greedy),
+                    RepetitionKind::ZeroOrOne=>Automaton::automaton_zero_or_one(&automaton(),greedy),
+                    RepetitionKind::Exactly(n)=>Automaton::automaton_repeat(automaton, n, Some(n),greedy),
+                    RepetitionKind::AtLeast(n)=>Automaton::automaton_repeat(automaton, n, None,greedy),
+                    RepetitionKind::Bounded(n,m)=>Automaton::automaton_repeat(automaton, n, Some(m),greedy),
+                }
+            },
+            Ast::Group(group)=>Automaton::automaton_capture(&NFA::compile(&group.ast), group.index),
+            Ast::Backreference(_)|Ast::LookAround(_)|Ast::Atomic(_)=>panic!("{}",ErrorKind::NeedsBacktracking),
         }
-    
-        Ok(NFA{automaton:stack.pop().unwrap()})
     }
 }
 
 
+/*
+Pike VM threads in priority order with the capture slots of the path that reached them,
+slot 0 holds where the thread's match started.
+A state reached again by a lower priority path is ignored
+*/
+#[derive(Default)]
+struct Threads{
+    list:Vec<(State,Vec<Option<usize>>)>,
+    seen:HashSet<usize>
+}
+
+impl Threads {
+    /*
+    follows epsilon edges depth first so the preferred edge of a split is added first,
+    look edges are followed when they hold between behind and next and save edges record at
+    */
+    fn add(&mut self,state:State,slots:Vec<Option<usize>>,at:usize,behind:LookBehind,next:Option<char>){
+        let mut stack=vec![(state,slots)];
+        while let Some((state,slots))=stack.pop(){
+            if !self.seen.insert(state.get_id()) {
+                continue;
+            }
+            let adjacent=state.adjacent();
+            let zero_width=adjacent.iter().any(|(symbol,_)| matches!(symbol,Symbol::Epsilon|Symbol::Look(_)|Symbol::Save(_)));
+            if !zero_width {
+                self.list.push((state,slots));
+                continue;
+            }
+
+            for (symbol,to) in adjacent.into_iter().rev(){
+                let Some(to)=to else {
+                    continue;
+                };
+                let to=to.borrow().clone();
+                match symbol {
+                    Symbol::Epsilon=>stack.push((to,slots.clone())),
+                    Symbol::Look(look) if look.matches(behind, next)=>stack.push((to,slots.clone())),
+                    Symbol::Save(slot)=>{
+                        let mut slots=slots.clone();
+                        if slots.len()<=slot {
+                            slots.resize(slot+1, None);
+                        }
+                        slots[slot]=Some(at);
+                        stack.push((to,slots));
+                    }
+                    _=>{}
+                }
+            }
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests{
-    use crate::regex::automaton::state::{State,Symbol::Alphabet};
+    use crate::regex::automaton::state::{Class,State,Symbol::Alphabet};
     use super::*;
     
 
@@ -73,7 +237,7 @@ mod tests{
         let a3=Automaton{start_state:State::new_transition_ref(Alphabet('c'), Some(accept3.clone())),end_state:accept3};
         let a=Automaton::concat_automaton(&a1, &a2);
         let expect=NFA{automaton:Automaton::concat_automaton(&a, &a3)};
-        let result=NFA::construct_nfa(&re.to_string()).unwrap();
+        let result=NFA::construct_nfa(re).unwrap();
         
         assert_eq!(result,expect)
     }
@@ -87,9 +251,9 @@ mod tests{
         let a1=Automaton{start_state:State::new_transition_ref(Alphabet('a'), Some(accept1.clone())),end_state:accept1};
         let a2=Automaton{start_state:State::new_transition_ref(Alphabet('b'), Some(accept2.clone())),end_state:accept2};
         let a3=Automaton{start_state:State::new_transition_ref(Alphabet('c'), Some(accept3.clone())),end_state:accept3};
-        let a=Automaton::automaton_alternate(&a2, &a3);
-        let expect=NFA{automaton:Automaton::concat_automaton(&a1, &a)};
-        let result=NFA::construct_nfa(&re.to_string()).unwrap();
+        let a=Automaton::concat_automaton(&a1, &a2);
+        let expect=NFA{automaton:Automaton::automaton_alternate(&a, &a3)};
+        let result=NFA::construct_nfa(re).unwrap();
         
         assert_eq!(result,expect)
     }
@@ -103,10 +267,10 @@ mod tests{
         let a1=Automaton{start_state:State::new_transition_ref(Alphabet('a'), Some(accept1.clone())),end_state:accept1};
         let a2=Automaton{start_state:State::new_transition_ref(Alphabet('b'), Some(accept2.clone())),end_state:accept2};
         let a3=Automaton{start_state:State::new_transition_ref(Alphabet('c'), Some(accept3.clone())),end_state:accept3};
-        let a2=Automaton::automaton_zero_or_one(&a2);
-        let a=Automaton::automaton_alternate(&a2, &a3);
-        let expect=NFA{automaton:Automaton::concat_automaton(&a1, &a)};
-        let result=NFA::construct_nfa(&re.to_string()).unwrap();
+        let a2=Automaton::automaton_zero_or_more(&a2,true);
+        let a=Automaton::concat_automaton(&a1, &a2);
+        let expect=NFA{automaton:Automaton::automaton_alternate(&a, &a3)};
+        let result=NFA::construct_nfa(re).unwrap();
         
         assert_eq!(result,expect)
     }
@@ -121,9 +285,10 @@ mod tests{
         let a2=Automaton{start_state:State::new_transition_ref(Alphabet('b'), Some(accept2.clone())),end_state:accept2};
         let a3=Automaton{start_state:State::new_transition_ref(Alphabet('c'), Some(accept3.clone())),end_state:accept3};
         let ab=Automaton::concat_automaton(&a1, &a2);
-        let ab=Automaton::automaton_zero_or_one(&ab);
+        let ab=Automaton::automaton_capture(&ab,1);
+        let ab=Automaton::automaton_zero_or_more(&ab,true);
         let expect=NFA{automaton:Automaton::automaton_alternate(&ab, &a3)};
-        let result=NFA::construct_nfa(&re.to_string()).unwrap();
+        let result=NFA::construct_nfa(re).unwrap();
         
         assert_eq!(result,expect)
     }
@@ -135,13 +300,141 @@ mod tests{
         let accept2=State::new_accept_ref();
         let accept3=State::new_accept_ref();
         let a1=Automaton{start_state:State::new_transition_ref(Alphabet('a'), Some(accept1.clone())),end_state:accept1};
-        let a2=Automaton{start_state:State::new_transition_ref(Symbol::new_all_char(), Some(accept2.clone())),end_state:accept2};
+        let a2=Automaton{start_state:State::new_transition_ref(Symbol::CharClass(Class::AllExceptNewline), Some(accept2.clone())),end_state:accept2};
         let a3=Automaton{start_state:State::new_transition_ref(Alphabet('c'), Some(accept3.clone())),end_state:accept3};
         let ab=Automaton::concat_automaton(&a1, &a2);
-        let ab=Automaton::automaton_zero_or_one(&ab);
+        let ab=Automaton::automaton_capture(&ab,1);
+        let ab=Automaton::automaton_zero_or_more(&ab,true);
         let expect=NFA{automaton:Automaton::automaton_alternate(&ab, &a3)};
-        let result=NFA::construct_nfa(&re.to_string()).unwrap();
+        let result=NFA::construct_nfa(re).unwrap();
         
         assert_eq!(result,expect)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn build_plus_question() {
+        let re="a+|b?";
+        let accept1=State::new_accept_ref();
+        let accept2=State::new_accept_ref();
+        let a1=Automaton{start_state:State::new_transition_ref(Alphabet('a'), Some(accept1.clone())),end_state:accept1};
+        let a2=Automaton{start_state:State::new_transition_ref(Alphabet('b'), Some(accept2.clone())),end_state:accept2};
+        let a1=Automaton::automaton_one_or_more(&a1,true);
+        let a2=Automaton::automaton_zero_or_one(&a2,true);
+        let expect=NFA{automaton:Automaton::automaton_alternate(&a1, &a2)};
+        let result=NFA::construct_nfa(re).unwrap();
+        
+        assert_eq!(result,expect)
+    }
+
+    #[test]
+    fn build_counted() {
+        let re="a{2,4}";
+        let a=||{
+            let accept=State::new_accept_ref();
+            Automaton{start_state:State::new_transition_ref(Alphabet('a'), Some(accept.clone())),end_state:accept}
+        };
+        let optional=Automaton::automaton_zero_or_one(&a(),true);
+        let optional=Automaton::automaton_zero_or_one(&Automaton::concat_automaton(&a(), &optional),true);
+        let required=Automaton::concat_automaton(&a(), &a());
+        let expect=NFA{automaton:Automaton::concat_automaton(&required, &optional)};
+        let result=NFA::construct_nfa(re).unwrap();
+        
+        assert_eq!(result,expect);
+
+        let re="a{2,}";
+        let expect=NFA{automaton:Automaton::concat_automaton(&a(), &Automaton::automaton_one_or_more(&a(),true))};
+        let result=NFA::construct_nfa(re).unwrap();
+        
+        assert_eq!(result,expect)
+    }
+
+    #[test]
+    fn find_greedy_lazy() {
+        let find=|re:&str,haystack:&str| NFA::construct_nfa(re).unwrap().find(haystack);
+
+        assert_eq!(find("a+","baaa"),Some(1..4));
+        assert_eq!(find("a+?","baaa"),Some(1..2));
+        assert_eq!(find("a*","baaa"),Some(0..0));
+        assert_eq!(find("ba*?","baaa"),Some(0..1));
+        assert_eq!(find("ba??","baaa"),Some(0..1));
+        assert_eq!(find("ba{1,2}?","baaa"),Some(0..2));
+        assert_eq!(find("ba{2,}?","baaa"),Some(0..3));
+        assert_eq!(find("<.*>","<a><b>"),Some(0..6));
+        assert_eq!(find("<.*?>","<a><b>"),Some(0..3));
+        assert_eq!(find("(?:a?|b)*","b"),Some(0..1));
+        assert_eq!(find("(a?|b)*","bb"),Some(0..2));
+        assert_eq!(find("(?:a?|b)+","b"),Some(0..0));
+        assert_eq!(find("(?:a?|b)+","ab"),Some(0..2));
+        assert_eq!(find("(?:a?|b){2,}","bbb"),Some(0..0));
+        assert_eq!(find("(?:a*?)+","aaa"),Some(0..0));
+        assert_eq!(find("(?:a*?)+b","aab"),Some(0..3));
+        assert_eq!(find("(?:a*?){2,}","aaa"),Some(0..0));
+        assert_eq!(find("(?:a+?)+","aaa"),Some(0..3));
+        assert_eq!(find("x","abc"),None);
+    }
+
+    #[test]
+    fn find_leftmost_first() {
+        let find=|re:&str,haystack:&str| NFA::construct_nfa(re).unwrap().find(haystack);
+
+        assert_eq!(find("ab|abcd","xabcd"),Some(1..3));
+        assert_eq!(find("abcd|ab","xabcd"),Some(1..5));
+        assert_eq!(find("é+","aééb"),Some(1..5));
+        assert_eq!(find("","abc"),Some(0..0));
+        assert_eq!(find("(?:^|\\w)*","ab"),Some(0..2));
+        assert_eq!(find("(?:|a)*","aa"),Some(0..2));
+    }
+
+    #[test]
+    fn find_leftmost_longest() {
+        let find=|re:&str,haystack:&str| NFA::construct_nfa(re).unwrap().find_longest(haystack);
+
+        assert_eq!(find("ab|abcd","xabcd"),Some(1..5));
+        assert_eq!(find("a*?","aaab"),Some(0..3));
+        assert_eq!(find("b|abc","abcd"),Some(0..3));
+        assert_eq!(find("bcd|a","abcd"),Some(0..1));
+        assert_eq!(find("(a|ab)(c|bcd)","xabcd"),Some(1..5));
+        assert_eq!(find("x","abc"),None);
+    }
+
+    #[test]
+    fn find_anchors() {
+        let find=|re:&str,haystack:&str| NFA::construct_nfa(re).unwrap().find(haystack);
+
+        assert_eq!(find("^a","ba"),None);
+        assert_eq!(find("^a","ab"),Some(0..1));
+        assert_eq!(find("a$","aba"),Some(2..3));
+        assert_eq!(find("a*$","baa"),Some(1..3));
+        assert_eq!(find("$","abc"),Some(3..3));
+        assert_eq!(find("\\Ab|b\\z","abcb"),Some(3..4));
+
+        let multi_line=crate::regex::transform::Flags{multi_line:true,..Default::default()};
+        let ast=crate::regex::transform::parse_with_flags("^b.$", multi_line).unwrap();
+        assert_eq!(NFA::from_ast(&ast).find("ab\nbc\nbd"),Some(3..5));
+    }
+
+    #[test]
+    fn find_word_boundary() {
+        let find=|re:&str,haystack:&str| NFA::construct_nfa(re).unwrap().find(haystack);
+
+        assert_eq!(find("\\bfoo\\b","foobar barfoo foo"),Some(14..17));
+        assert_eq!(find("\\Bfoo","foo barfoo"),Some(7..10));
+        assert_eq!(find("\\b","  ab"),Some(2..2));
+        assert_eq!(find("\\bé\\w*","café élan"),Some(6..11));
+
+        let ascii=crate::regex::transform::Flags{unicode:false,..Default::default()};
+        let ast=crate::regex::transform::parse_with_flags("\\bé", ascii).unwrap();
+        assert_eq!(NFA::from_ast(&ast).find("café"),Some(3..5));
+    }
+
+    #[test]
+    fn find_captures() {
+        let captures=|re:&str,haystack:&str| NFA::construct_nfa(re).unwrap().captures(haystack);
+
+        assert_eq!(captures("(a)(b)?","xa"),Some(vec![Some(1),Some(2),Some(1),Some(2)]));
+        assert_eq!(captures("(a|(b))+","ab"),Some(vec![Some(0),Some(2),Some(1),Some(2),Some(1),Some(2)]));
+        assert_eq!(captures("(?:a(b))c","abc"),Some(vec![Some(0),Some(3),Some(1),Some(2)]));
+        assert_eq!(captures("(a*)*","b"),Some(vec![Some(0),Some(0)]));
+        assert_eq!(captures("(a)","b"),None);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{cell::RefCell, rc::Rc, sync::atomic::{AtomicUsize, Ordering}, collections::{HashSet, VecDeque}, fmt::Debug};
 
-pub use symbols::Symbol;
+pub use symbols::{Symbol,Class,ClassSet,ClassRange,Look,PerlClass,PerlClassKind,UnicodeClass,UnicodeProperty};
+pub(crate) use symbols::{next_char,prev_char,LookBehind};
 
 mod symbols;
 
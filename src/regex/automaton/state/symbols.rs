@@ -1,4 +1,3 @@
-
 #[derive(Clone,Debug,PartialEq, Eq, PartialOrd, Ord)]
 //#[repr(u8)] this adds u8 to distinguish enum variants. This increase size from alignment to 2*alignment
 //
@@ -6,11 +5,106 @@ pub enum Symbol {
     Alphabet(char),
     CharClass(Class),
     Epsilon,
+    Look(Look),
+    /*epsilon edge that records the current position in a capture slot*/
+    Save(usize),
 }
 
+use std::collections::BTreeSet;
+
+use crate::regex::unicode_tables::{self,general_category,perl_decimal,perl_space,perl_word,property_values,script};
+
 #[derive( Clone,Debug,PartialEq, Eq, PartialOrd, Ord)]
 pub enum Class{
-    All
+    All,
+    /* . outside dot-all mode */
+    AllExceptNewline,
+    Set(ClassSet),
+    Perl(PerlClass),
+    Unicode(UnicodeClass)
+}
+
+/*
+\d \w \s and their negations, unicode selects the UCD definitions over the ASCII ones
+*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq, PartialOrd, Ord)]
+pub struct PerlClass{
+    pub kind:PerlClassKind,
+    pub negated:bool,
+    pub unicode:bool
+}
+
+#[derive(Clone,Copy,Debug,PartialEq, Eq, PartialOrd, Ord)]
+pub enum PerlClassKind{
+    Digit,
+    Word,
+    Space
+}
+
+/*
+\p{..} and \P{..}, the property holds the canonical UCD name of its value
+*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnicodeClass{
+    pub property:UnicodeProperty,
+    pub negated:bool
+}
+
+#[derive(Clone,Copy,Debug,PartialEq, Eq, PartialOrd, Ord)]
+pub enum UnicodeProperty{
+    GeneralCategory(&'static str),
+    Script(&'static str)
+}
+
+/*
+Zero width assertions, an edge labeled with one is an epsilon edge that is only taken
+at positions where the assertion holds
+*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Look{
+    /* \A, and ^ outside multi-line mode */
+    Start,
+    /* \z, and $ outside multi-line mode */
+    End,
+    /* ^ in multi-line mode */
+    StartLine,
+    /* $ in multi-line mode */
+    EndLine,
+    /* \b and \B with the Unicode definition of a word char */
+    WordBoundaryUnicode,
+    NotWordBoundaryUnicode,
+    /* \b and \B with word chars limited to [0-9A-Za-z_] */
+    WordBoundaryAscii,
+    NotWordBoundaryAscii
+}
+
+/*
+What a look can ask about the char before a position
+*/
+#[derive(Clone,Copy,Debug,Default,PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LookBehind{
+    pub start:bool,
+    pub newline:bool,
+    pub word_unicode:bool,
+    pub word_ascii:bool
+}
+
+/*
+Inclusive range of chars, start<=end always holds
+*/
+#[derive(Clone,Copy,Debug,PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClassRange{
+    pub start:char,
+    pub end:char
+}
+
+/*
+Sorted ranges where no two ranges overlap or touch, so membership is a binary search
+and two sets with the same chars always compare equal
+*/
+#[derive(Clone,Debug,Default,PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClassSet{
+    ranges:Vec<ClassRange>
 }
 
 impl Symbol{
@@ -24,33 +118,444 @@ impl Symbol{
         Self::CharClass(Class::All)
     }
 
+    /*single char sets become plain alphabet symbols*/
+    pub fn from_set(set:ClassSet)->Symbol{
+        match set.ranges() {
+            [range] if range.start==range.end=>Symbol::Alphabet(range.start),
+            _=>Symbol::CharClass(Class::Set(set))
+        }
+    }
+
     pub fn contains(&self,other:&Self)->bool{
         match self {
             Symbol::Alphabet(c0) => {
                 match other {
                     Symbol::Alphabet(c1) => c0==c1,
                     Symbol::CharClass(char_class) => char_class.contains(c0),
-                    Symbol::Epsilon => false,
+                    Symbol::Epsilon|Symbol::Look(_)|Symbol::Save(_) => false,
                 }
             },
             Symbol::CharClass(char_class) => {
                 match other {
                     Symbol::Alphabet(c1) => char_class.contains(c1),
-                    Symbol::CharClass(char_class) => false,
-                    Symbol::Epsilon => false,
+                    Symbol::CharClass(_) => false,
+                    Symbol::Epsilon|Symbol::Look(_)|Symbol::Save(_) => false,
                 }
             },
             Symbol::Epsilon => matches!(other,Symbol::Epsilon),
+            Symbol::Look(look) => matches!(other,Symbol::Look(other) if other==look),
+            /*only the Pike VM cares about slots, everything else sees a plain epsilon*/
+            Symbol::Save(_) => matches!(other,Symbol::Epsilon),
+        }
+    }
+
+    /*chars this symbol can consume, None for epsilon, looks and saves*/
+    pub fn to_set(&self)->Option<ClassSet>{
+        match self {
+            Symbol::Alphabet(c) => Some(ClassSet::from_char(*c)),
+            Symbol::CharClass(char_class) => Some(char_class.to_set()),
+            Symbol::Epsilon|Symbol::Look(_)|Symbol::Save(_) => None,
         }
     }
 }
 
 impl Class {
-    fn contains(&self,c:&char)->bool{
+    pub fn contains(&self,c:&char)->bool{
         match self {
             Class::All => {
                 true
             },
+            Class::AllExceptNewline => {
+                *c!='\n'
+            },
+            Class::Set(set) => {
+                set.contains(*c)
+            },
+            Class::Perl(perl) => {
+                perl.contains(*c)
+            },
+            Class::Unicode(unicode) => {
+                unicode.contains(*c)
+            },
+        }
+    }
+
+    pub fn to_set(&self)->ClassSet{
+        match self {
+            Class::All => ClassSet::full(),
+            Class::AllExceptNewline => {
+                let mut set=ClassSet::from_char('\n');
+                set.negate();
+                set
+            },
+            Class::Set(set) => set.clone(),
+            Class::Perl(perl) => perl.to_set(),
+            Class::Unicode(unicode) => unicode.to_set(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl PerlClass {
+    pub fn new(kind:PerlClassKind,negated:bool,unicode:bool)->PerlClass{
+        PerlClass{kind,negated,unicode}
+    }
+
+    pub fn contains(&self,c:char)->bool{
+        let contains=match (self.kind,self.unicode) {
+            (PerlClassKind::Digit,false) => c.is_ascii_digit(),
+            (PerlClassKind::Word,false) => c.is_ascii_alphanumeric()||c=='_',
+            (PerlClassKind::Space,false) => matches!(c,'\t'|'\n'|'\u{B}'|'\u{C}'|'\r'|' '),
+            (kind,true) => unicode_tables::table_contains(PerlClass::table(kind), c),
+        };
+        contains!=self.negated
+    }
+
+    pub fn to_set(&self)->ClassSet{
+        let mut set=match (self.kind,self.unicode) {
+            (PerlClassKind::Digit,false) => ClassSet::new([ClassRange::new('0','9')]),
+            (PerlClassKind::Word,false) => ClassSet::new([
+                ClassRange::new('0','9'),
+                ClassRange::new('A','Z'),
+                ClassRange::new('_','_'),
+                ClassRange::new('a','z')
+            ]),
+            (PerlClassKind::Space,false) => ClassSet::new([ClassRange::new('\t','\r'),ClassRange::new(' ',' ')]),
+            (kind,true) => unicode_tables::table_to_set(PerlClass::table(kind)),
+        };
+        if self.negated {
+            set.negate();
+        }
+        set
+    }
+
+    fn table(kind:PerlClassKind)->&'static [(char,char)]{
+        match kind {
+            PerlClassKind::Digit => perl_decimal::DECIMAL_NUMBER,
+            PerlClassKind::Word => perl_word::PERL_WORD,
+            PerlClassKind::Space => perl_space::WHITE_SPACE,
+        }
+    }
+}
+
+impl UnicodeClass {
+    pub fn new(property:UnicodeProperty,negated:bool)->UnicodeClass{
+        UnicodeClass{property,negated}
+    }
+
+    pub fn contains(&self,c:char)->bool{
+        unicode_tables::table_contains(self.property.table(), c)!=self.negated
+    }
+
+    pub fn to_set(&self)->ClassSet{
+        let mut set=unicode_tables::table_to_set(self.property.table());
+        if self.negated {
+            set.negate();
+        }
+        set
+    }
+}
+
+impl UnicodeProperty {
+    /*
+    Accepts general categories and scripts by any of their aliases (L, Letter, Lu, Greek, Grek)
+    and the explicit gc=.. / sc=.. forms
+    */
+    pub fn from_name(name:&str)->Option<UnicodeProperty>{
+        let general_category=|value:&str|{
+            unicode_tables::canonical_name(property_values::GENERAL_CATEGORY, value)
+            .filter(|name| unicode_tables::by_name(general_category::BY_NAME, name).is_some())
+            .map(UnicodeProperty::GeneralCategory)
+        };
+        let script=|value:&str|{
+            unicode_tables::canonical_name(property_values::SCRIPT, value)
+            .map(UnicodeProperty::Script)
+        };
+
+        match name.split_once(['=',':']) {
+            Some((key,value))=>{
+                match unicode_tables::canonical_name(&[("gc","gc"),("generalcategory","gc"),("sc","sc"),("script","sc")], key) {
+                    Some("gc")=>general_category(value),
+                    Some("sc")=>script(value),
+                    _=>None
+                }
+            }
+            None=>general_category(name).or_else(|| script(name))
+        }
+    }
+
+    pub fn name(&self)->&'static str{
+        match self {
+            UnicodeProperty::GeneralCategory(name) => name,
+            UnicodeProperty::Script(name) => name,
+        }
+    }
+
+    fn table(&self)->&'static [(char,char)]{
+        let table=match self {
+            UnicodeProperty::GeneralCategory(name) => unicode_tables::by_name(general_category::BY_NAME, name),
+            UnicodeProperty::Script(name) => unicode_tables::by_name(script::BY_NAME, name),
+        };
+        table.unwrap_or(&[])
+    }
+}
+
+impl Look {
+    /*behind describes the char before the position and next is the char after it*/
+    pub fn matches(&self,behind:LookBehind,next:Option<char>)->bool{
+        match self {
+            Look::Start => behind.start,
+            Look::End => next.is_none(),
+            Look::StartLine => behind.start||behind.newline,
+            Look::EndLine => next.is_none()||next==Some('\n'),
+            Look::WordBoundaryUnicode => behind.word_unicode!=Look::is_word(next, true),
+            Look::NotWordBoundaryUnicode => behind.word_unicode==Look::is_word(next, true),
+            Look::WordBoundaryAscii => behind.word_ascii!=Look::is_word(next, false),
+            Look::NotWordBoundaryAscii => behind.word_ascii==Look::is_word(next, false),
+        }
+    }
+
+    fn is_word(c:Option<char>,unicode:bool)->bool{
+        c.is_some_and(|c| PerlClass::new(PerlClassKind::Word, false, unicode).contains(c))
+    }
+
+    /*chars the look tells apart from the rest, an automaton's alphabet is split along them*/
+    pub fn char_sets(&self)->Vec<ClassSet>{
+        match self {
+            Look::Start|Look::End => vec![],
+            Look::StartLine|Look::EndLine => vec![ClassSet::from_char('\n')],
+            Look::WordBoundaryUnicode|Look::NotWordBoundaryUnicode => Look::word_sets(true),
+            Look::WordBoundaryAscii|Look::NotWordBoundaryAscii => Look::word_sets(false),
+        }
+    }
+
+    /*non word chars are kept as well, a boundary can come right before any of them*/
+    fn word_sets(unicode:bool)->Vec<ClassSet>{
+        let word=PerlClass::new(PerlClassKind::Word, false, unicode);
+        let not_word=PerlClass::new(PerlClassKind::Word, true, unicode);
+        vec![word.to_set(),not_word.to_set()]
+    }
+}
+
+impl LookBehind {
+    pub fn new(prev:Option<char>)->LookBehind{
+        LookBehind{
+            start:prev.is_none(),
+            newline:prev==Some('\n'),
+            word_unicode:Look::is_word(prev, true),
+            word_ascii:Look::is_word(prev, false)
+        }
+    }
+
+    /*forgets what none of looks asks about, so positions that no look tells apart compare equal*/
+    pub fn restrict(&self,looks:&BTreeSet<Look>)->LookBehind{
+        let mut restricted=LookBehind::default();
+        for look in looks{
+            match look {
+                Look::Start => {
+                    restricted.start=self.start;
+                },
+                Look::StartLine => {
+                    restricted.start=self.start;
+                    restricted.newline=self.newline;
+                },
+                Look::WordBoundaryUnicode|Look::NotWordBoundaryUnicode => {
+                    restricted.word_unicode=self.word_unicode;
+                },
+                Look::WordBoundaryAscii|Look::NotWordBoundaryAscii => {
+                    restricted.word_ascii=self.word_ascii;
+                },
+                Look::End|Look::EndLine => {}
+            }
+        }
+        restricted
+    }
+}
+
+impl ClassRange {
+    pub fn new(start:char,end:char)->ClassRange{
+        if start<=end {
+            ClassRange{start,end}
+        }
+        else {
+            ClassRange{start:end,end:start}
+        }
+    }
+
+    pub fn contains(&self,c:char)->bool{
+        self.start<=c&&c<=self.end
+    }
+}
+
+impl ClassSet {
+    pub fn new<I:IntoIterator<Item=ClassRange>>(ranges:I)->ClassSet{
+        let mut set=ClassSet{ranges:ranges.into_iter().collect()};
+        set.canonicalize();
+        set
+    }
+
+    pub fn empty()->ClassSet{
+        ClassSet{ranges:Vec::new()}
+    }
+
+    pub fn full()->ClassSet{
+        ClassSet{ranges:vec![ClassRange::new('\0', char::MAX)]}
+    }
+
+    pub fn from_char(c:char)->ClassSet{
+        ClassSet{ranges:vec![ClassRange::new(c, c)]}
+    }
+
+    pub fn ranges(&self)->&[ClassRange]{
+        &self.ranges
+    }
+
+    pub fn is_empty(&self)->bool{
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self,c:char)->bool{
+        self.ranges
+        .binary_search_by(|range|{
+            if range.end<c {
+                std::cmp::Ordering::Less
+            }
+            else if range.start>c {
+                std::cmp::Ordering::Greater
+            }
+            else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+    }
+
+    pub fn push(&mut self,range:ClassRange){
+        self.ranges.push(range);
+        self.canonicalize();
+    }
+
+    pub fn union(&mut self,other:&ClassSet){
+        self.ranges.extend_from_slice(&other.ranges);
+        self.canonicalize();
+    }
+
+    /*keeps the chars in both sets, walks both sorted range lists once*/
+    pub fn intersect(&mut self,other:&ClassSet){
+        let mut intersection=Vec::new();
+        let (mut i,mut j)=(0,0);
+        while i<self.ranges.len()&&j<other.ranges.len() {
+            let (a,b)=(self.ranges[i],other.ranges[j]);
+            let start=a.start.max(b.start);
+            let end=a.end.min(b.end);
+            if start<=end {
+                intersection.push(ClassRange::new(start, end));
+            }
+            if a.end<b.end {
+                i+=1;
+            }
+            else {
+                j+=1;
+            }
+        }
+        self.ranges=intersection;
+    }
+
+    pub fn difference(&mut self,other:&ClassSet){
+        let mut complement=other.clone();
+        complement.negate();
+        self.intersect(&complement);
+    }
+
+    /*chars in exactly one of the sets*/
+    pub fn symmetric_difference(&mut self,other:&ClassSet){
+        let mut both=self.clone();
+        both.intersect(other);
+        self.union(other);
+        self.difference(&both);
+    }
+
+    /*
+    ASCII classes written [:name:] inside brackets, same definitions as POSIX in the C locale
+    with word and ascii as extras
+    */
+    pub fn posix(name:&str)->Option<ClassSet>{
+        let ranges:&[(char,char)]=match name {
+            "alnum" => &[('0','9'),('A','Z'),('a','z')],
+            "alpha" => &[('A','Z'),('a','z')],
+            "ascii" => &[('\0','\x7F')],
+            "blank" => &[('\t','\t'),(' ',' ')],
+            "cntrl" => &[('\0','\x1F'),('\x7F','\x7F')],
+            "digit" => &[('0','9')],
+            "graph" => &[('!','~')],
+            "lower" => &[('a','z')],
+            "print" => &[(' ','~')],
+            "punct" => &[('!','/'),(':','@'),('[','`'),('{','~')],
+            "space" => &[('\t','\r'),(' ',' ')],
+            "upper" => &[('A','Z')],
+            "word" => &[('0','9'),('A','Z'),('_','_'),('a','z')],
+            "xdigit" => &[('0','9'),('A','F'),('a','f')],
+            _ => return None
+        };
+        Some(ClassSet::new(ranges.iter().map(|(start,end)| ClassRange::new(*start, *end))))
+    }
+
+    /*complement over every char, the surrogate gap is skipped by char arithmetic*/
+    pub fn negate(&mut self){
+        let mut negated=Vec::new();
+        let mut next_start=Some('\0');
+        for range in &self.ranges{
+            if let Some(start)=next_start {
+                if start<range.start {
+                    negated.push(ClassRange::new(start, prev_char(range.start).expect("range.start above start")));
+                }
+            }
+            next_start=next_char(range.end);
+        }
+        if let Some(start)=next_start {
+            negated.push(ClassRange::new(start, char::MAX));
+        }
+        self.ranges=negated;
+    }
+
+    /*adds every char that simple case folding maps to or from a char of the set*/
+    pub fn case_fold(&mut self){
+        let folds:Vec<ClassRange>=
+        self.ranges
+        .iter()
+        .flat_map(|range| unicode_tables::case_folds_between(range.start, range.end))
+        .flat_map(|(_,folds)| folds.iter().map(|c| ClassRange::new(*c, *c)))
+        .collect();
+        self.ranges.extend(folds);
+        self.canonicalize();
+    }
+
+    fn canonicalize(&mut self){
+        self.ranges.sort();
+        let mut merged:Vec<ClassRange>=Vec::with_capacity(self.ranges.len());
+        for range in self.ranges.drain(..){
+            match merged.last_mut() {
+                Some(last) if next_char(last.end).is_none_or(|next| range.start<=next)=>{
+                    last.end=last.end.max(range.end);
+                }
+                _=>merged.push(range)
+            }
+        }
+        self.ranges=merged;
+    }
+}
+
+pub fn next_char(c:char)->Option<char>{
+    match c {
+        '\u{D7FF}'=>Some('\u{E000}'),
+        char::MAX=>None,
+        _=>char::from_u32(c as u32+1)
+    }
+}
+
+pub fn prev_char(c:char)->Option<char>{
+    match c {
+        '\u{E000}'=>Some('\u{D7FF}'),
+        '\0'=>None,
+        _=>char::from_u32(c as u32-1)
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::{self,Display};
+use std::ops::Range;
+
+#[derive(Clone,Debug,PartialEq, Eq)]
+pub struct Error{
+    kind:ErrorKind,
+    pattern:String,
+    span:Range<usize>
+}
+
+#[derive(Clone,Debug,PartialEq, Eq)]
+pub enum ErrorKind{
+    UnclosedGroup,
+    UnopenedGroup,
+    DanglingRepetition,
+    EmptyEscape,
+    UnclosedClass,
+    InvalidClassRange,
+    InvalidClassEscape,
+    UnclosedUnicodeClass,
+    UnicodePropertyNotFound,
+    UnclosedCountedRepetition,
+    InvalidCountedRepetition,
+    RepetitionTooLarge,
+    UnrecognizedFlag,
+    MissingFlags,
+    InvalidGroupName,
+    DuplicateGroupName,
+    UnrecognizedEscape,
+    InvalidHexEscape,
+    MissingClassOperand,
+    UnrecognizedPosixClass,
+    InvalidBackreference,
+    NeedsBacktracking,
+    BacktrackLimitExceeded,
+    UnboundedLookBehind,
+    UnclosedBrace,
+    UnsupportedInDialect,
+}
+
+impl Error {
+    pub fn new(kind:ErrorKind,pattern:&str,span:Range<usize>)->Error{
+        Error{
+            kind,
+            pattern:pattern.to_string(),
+            span
+        }
+    }
+
+    pub fn kind(&self)->&ErrorKind{
+        &self.kind
+    }
+
+    pub fn pattern(&self)->&str{
+        &self.pattern
+    }
+
+    /*byte offsets into the pattern*/
+    pub fn span(&self)->Range<usize>{
+        self.span.clone()
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description=match self {
+            ErrorKind::UnclosedGroup => "unclosed group",
+            ErrorKind::UnopenedGroup => "unopened group",
+            ErrorKind::DanglingRepetition => "repetition operator missing expression",
+            ErrorKind::EmptyEscape => "incomplete escape sequence, reached end of pattern prematurely",
+            ErrorKind::UnclosedClass => "unclosed character class",
+            ErrorKind::InvalidClassRange => "invalid character class range, the start must be <= the end",
+            ErrorKind::InvalidClassEscape => "escape sequence is not valid in a character class",
+            ErrorKind::UnclosedUnicodeClass => "unclosed unicode class, expected '}'",
+            ErrorKind::UnicodePropertyNotFound => "unicode property not found",
+            ErrorKind::UnclosedCountedRepetition => "unclosed counted repetition, expected '}'",
+            ErrorKind::InvalidCountedRepetition => "invalid counted repetition, expected {n}, {n,} or {n,m} with n <= m",
+            ErrorKind::RepetitionTooLarge => "repetition expands beyond the configured size limit",
+            ErrorKind::UnrecognizedFlag => "unrecognized flag",
+            ErrorKind::MissingFlags => "expected a flag",
+            ErrorKind::InvalidGroupName => "invalid capture group name",
+            ErrorKind::DuplicateGroupName => "duplicate capture group name",
+            ErrorKind::UnrecognizedEscape => "unrecognized escape sequence",
+            ErrorKind::InvalidHexEscape => "invalid hexadecimal escape, expected \\xHH, \\uHHHH or a braced scalar value",
+            ErrorKind::MissingClassOperand => "class set operator missing an operand",
+            ErrorKind::UnrecognizedPosixClass => "unrecognized POSIX character class",
+            ErrorKind::InvalidBackreference => "backreference to a group that hasn't been opened",
+            ErrorKind::NeedsBacktracking => "backreferences, look-arounds and atomic groups can't be compiled to an automaton",
+            ErrorKind::BacktrackLimitExceeded => "backtracking search ran out of steps",
+            ErrorKind::UnboundedLookBehind => "look-behind has no upper bound on its length",
+            ErrorKind::UnclosedBrace => "unclosed brace alternation, expected '}'",
+            ErrorKind::UnsupportedInDialect => "not valid in the selected regex dialect",
+        };
+        write!(f,"{description}")
+    }
+}
+
+/*
+regex parse error:
+    a(bc
+     ^
+error: unclosed group
+*/
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_start=self.pattern[..self.span.start].rfind('\n').map_or(0, |i| i+1);
+        let line_end=self.pattern[self.span.start..].find('\n').map_or(self.pattern.len(), |i| self.span.start+i);
+        let line=&self.pattern[line_start..line_end];
+        let column=self.pattern[line_start..self.span.start].chars().count();
+        let width=self.pattern[self.span.start..self.span.end.min(line_end)].chars().count().max(1);
+
+        writeln!(f,"regex parse error:")?;
+        writeln!(f,"    {line}")?;
+        writeln!(f,"    {}{}"," ".repeat(column),"^".repeat(width))?;
+        write!(f,"error: {}",self.kind)
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn display_caret(){
+        let error=Error::new(ErrorKind::UnclosedGroup, "ab(cd", 2..3);
+        let expect="regex parse error:\n    ab(cd\n      ^\nerror: unclosed group";
+
+        assert_eq!(error.to_string(),expect)
+    }
+
+    #[test]
+    fn display_end_of_pattern(){
+        let error=Error::new(ErrorKind::EmptyEscape, "ab\\", 2..3);
+        let expect="regex parse error:\n    ab\\\n      ^\nerror: incomplete escape sequence, reached end of pattern prematurely";
+
+        assert_eq!(error.to_string(),expect)
+    }
+}
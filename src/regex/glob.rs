@@ -0,0 +1,319 @@
+use super::ast::{Ast,Class,ClassRange,ClassSet,Look,RepetitionKind};
+use super::automaton::{DFA,NFA};
+use super::error::{Error,ErrorKind};
+use super::transform::split_first;
+
+/*
+grammer:
+
+GLOB -> ITEM*
+ITEM -> '**' | '*' | '?' | CLASS | BRACES | '\' char | char
+BRACES -> '{' GLOB (',' GLOB)* '}'
+CLASS -> '[' ('!' | '^')? RANGE+ ']'
+RANGE -> char ('-' char)?
+
+'*', '?' and classes never match '/'.
+'**' as a whole path component matches any number of directories:
+followed by a '/' it also matches none and as the last component it matches everything below,
+anywhere else '**' is the same as '*'. The start of a brace alternative is the start of a component too
+*/
+
+/*
+A glob compiled to a DFA, it has to match the whole path
+*/
+#[derive(Debug)]
+pub struct Glob{
+    glob:String,
+    dfa:DFA
+}
+
+impl Glob {
+    pub fn new(glob:&str)->Result<Glob,Error>{
+        let ast=parse(glob)?;
+        let dfa=DFA::from_nfa(&NFA::from_ast(&ast));
+        Ok(Glob{glob:glob.to_string(),dfa})
+    }
+
+    pub fn as_str(&self)->&str{
+        &self.glob
+    }
+
+    pub fn is_match(&self,path:&str)->bool{
+        self.dfa.match_input(path)
+    }
+}
+
+/*the ast of a glob, anchored at both ends*/
+pub fn parse(glob:&str)->Result<Ast,Error>{
+    let parser=Parser{glob};
+    /*outside braces only an error stops before the end*/
+    let (ast,_)=parser.parse_glob(glob, false)?;
+
+    Ok(Ast::Concat(vec![Ast::Look(Look::Start),ast,Ast::Look(Look::End)]))
+}
+
+struct Parser<'a>{
+    glob:&'a str
+}
+
+impl<'a> Parser<'a> {
+    fn offset(&self,at:&str)->usize{
+        self.glob.len()-at.len()
+    }
+
+    /*points at the char starting at*/
+    fn error(&self,kind:ErrorKind,at:&str)->Error{
+        let start=self.offset(at);
+        let end=start+at.chars().next().map_or(0, char::len_utf8);
+        Error::new(kind, self.glob, start..end)
+    }
+
+    /*points from the start of from up to the start of to*/
+    fn error_between(&self,kind:ErrorKind,from:&str,to:&str)->Error{
+        Error::new(kind, self.glob, self.offset(from)..self.offset(to))
+    }
+
+    /*
+    GLOB -> ITEM*
+    inside braces it stops before ',' or '}'
+    */
+    fn parse_glob(&self,s:&'a str,in_braces:bool)->Result<(Ast,&'a str),Error>{
+        let mut items=Vec::new();
+        let mut remain=s;
+
+        while let Some((c,after))=split_first(remain){
+            let item;
+            (item,remain)=match c {
+                ','|'}' if in_braces=>break,
+                '*' if after.starts_with('*')=>self.parse_recursive(remain, items.is_empty()),
+                '*'=>(star(not_separator()),after),
+                '?'=>(not_separator(),after),
+                '['=>self.parse_class(remain)?,
+                '{'=>self.parse_braces(remain)?,
+                '\\'=>{
+                    let (escaped,after)=split_first(after).ok_or_else(|| self.error(ErrorKind::EmptyEscape, remain))?;
+                    (Ast::Literal(escaped),after)
+                }
+                _=>(Ast::Literal(c),after)
+            };
+            items.push(item);
+        }
+
+        Ok((Ast::concat(items),remain))
+    }
+
+    /*
+    '**', a whole path component when it starts the glob or a brace alternative or follows a '/'
+    and ends the glob or comes before a '/'
+    */
+    fn parse_recursive(&self,s:&'a str,starts_alternative:bool)->(Ast,&'a str){
+        let after=&s[2..];
+        let component_start=starts_alternative||self.glob[..self.offset(s)].ends_with('/');
+
+        if component_start&&after.is_empty() {
+            (star(Ast::Class(Class::All)),after)
+        }
+        else if let Some(after)=after.strip_prefix('/').filter(|_| component_start) {
+            let directory=Ast::Concat(vec![star(not_separator()),Ast::Literal('/')]);
+            (star(directory),after)
+        }
+        else {
+            (star(not_separator()),after)
+        }
+    }
+
+    /*
+    BRACES -> '{' GLOB (',' GLOB)* '}'
+    */
+    fn parse_braces(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (_,mut remain)=split_first(s).expect("caller checked for '{'");
+        let mut alternatives=Vec::new();
+
+        loop {
+            let alternative;
+            (alternative,remain)=self.parse_glob(remain, true)?;
+            alternatives.push(alternative);
+            remain=match split_first(remain) {
+                Some((',',after))=>after,
+                Some(('}',after))=>break Ok((Ast::alternation(alternatives),after)),
+                _=>break Err(self.error(ErrorKind::UnclosedBrace, s))
+            };
+        }
+    }
+
+    /*
+    CLASS -> '[' ('!' | '^')? RANGE+ ']'
+    a ']' right after the opening bracket and a '-' at either end are literals,
+    a '\' escapes the next char
+    */
+    fn parse_class(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (_,mut remain)=split_first(s).expect("caller checked for '['");
+        let mut negated=false;
+        let mut ranges=Vec::new();
+
+        if let Some(('!'|'^',after))=split_first(remain){
+            negated=true;
+            remain=after;
+        }
+
+        loop {
+            let item=remain;
+            let start;
+            (start,remain)=match split_first(remain) {
+                Some((']',after)) if !ranges.is_empty()=>{
+                    remain=after;
+                    break;
+                }
+                _=>self.parse_class_char(s, remain)?
+            };
+
+            match split_first(remain) {
+                Some(('-',after)) if !after.is_empty()&&!after.starts_with(']')=>{
+                    let end;
+                    (end,remain)=self.parse_class_char(s, after)?;
+                    if end<start {
+                        return Err(self.error_between(ErrorKind::InvalidClassRange, item, remain));
+                    }
+                    ranges.push(ClassRange::new(start, end));
+                }
+                _=>ranges.push(ClassRange::new(start, start))
+            }
+        }
+
+        let mut set=ClassSet::new(ranges);
+        if negated {
+            set.negate();
+        }
+        set.difference(&ClassSet::from_char('/'));
+
+        Ok((Ast::Class(Class::Set(set)),remain))
+    }
+
+    fn parse_class_char(&self,s:&'a str,remain:&'a str)->Result<(char,&'a str),Error>{
+        match split_first(remain) {
+            Some(('\\',after))=>split_first(after).ok_or_else(|| self.error(ErrorKind::UnclosedClass, s)),
+            Some(next)=>Ok(next),
+            None=>Err(self.error(ErrorKind::UnclosedClass, s))
+        }
+    }
+}
+
+fn star(ast:Ast)->Ast{
+    Ast::repetition(RepetitionKind::ZeroOrMore, ast)
+}
+
+fn not_separator()->Ast{
+    let mut set=ClassSet::from_char('/');
+    set.negate();
+    Ast::Class(Class::Set(set))
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn is_match(glob:&str,path:&str)->bool{
+        Glob::new(glob).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn parse_anchored(){
+        assert_eq!(parse("a?").unwrap(),Ast::Concat(vec![
+            Ast::Look(Look::Start),
+            Ast::Concat(vec![Ast::Literal('a'),not_separator()]),
+            Ast::Look(Look::End)
+        ]));
+        assert_eq!(parse("").unwrap(),Ast::Concat(vec![Ast::Look(Look::Start),Ast::Empty,Ast::Look(Look::End)]));
+    }
+
+    #[test]
+    fn star_and_question(){
+        assert!(is_match("*.rs","main.rs"));
+        assert!(is_match("*.rs",".rs"));
+        assert!(!is_match("*.rs","src/main.rs"));
+        assert!(!is_match("*.rs","main.rsx"));
+        assert!(is_match("a?c","abc"));
+        assert!(!is_match("a?c","a/c"));
+        assert!(!is_match("a?c","ac"));
+        assert!(is_match("日?","日本"));
+    }
+
+    #[test]
+    fn recursive(){
+        let glob=Glob::new("src/**/*.rs").unwrap();
+        assert!(glob.is_match("src/lib.rs"));
+        assert!(glob.is_match("src/regex/automaton/dfa.rs"));
+        assert!(!glob.is_match("lib.rs"));
+        assert!(!glob.is_match("src/lib.rsx"));
+
+        assert!(is_match("**/*.rs","lib.rs"));
+        assert!(is_match("**/*.rs","a/b/lib.rs"));
+        assert!(is_match("target/**","target/debug/parse"));
+        assert!(!is_match("target/**","target"));
+        assert!(is_match("**","any/path/at/all"));
+        assert!(is_match("a**b","axyb"));
+        assert!(!is_match("a**b","ax/yb"));
+
+        assert!(is_match("{**/x,y}","a/b/x"));
+        assert!(is_match("{**/x,y}","x"));
+        assert!(is_match("{y,**/x}","a/x"));
+        assert!(!is_match("{**/x,y}","a/y"));
+        assert!(!is_match("\\{**/x","{a/b/x"));
+    }
+
+    #[test]
+    fn braces(){
+        let glob=Glob::new("*.{toml,json}").unwrap();
+        assert!(glob.is_match("Cargo.toml"));
+        assert!(glob.is_match("package.json"));
+        assert!(!glob.is_match("Cargo.lock"));
+
+        assert!(is_match("{src/**/,}*.{r{s,lib},md}","src/a/b.rlib"));
+        assert!(is_match("{src/**/,}*.{r{s,lib},md}","README.md"));
+        assert!(is_match("x{,y}","x"));
+        assert!(is_match("a,b","a,b"));
+    }
+
+    #[test]
+    fn classes(){
+        let glob=Glob::new("[!a]?.log").unwrap();
+        assert!(glob.is_match("b1.log"));
+        assert!(!glob.is_match("a1.log"));
+        assert!(!glob.is_match("/1.log"));
+
+        assert!(is_match("[a-c]*","build"));
+        assert!(is_match("[^a-c]*","debug"));
+        assert!(is_match("[]-]","]"));
+        assert!(is_match("[]-]","-"));
+        assert!(is_match("[\\]x]","]"));
+        assert!(is_match("[*?]","?"));
+        assert!(!is_match("[*?]","a"));
+        assert!(is_match("a[.-0]b","a.b"));
+        assert!(!is_match("a[.-0]b","a/b"));
+        assert!(!is_match("a[/]b","a/b"));
+    }
+
+    #[test]
+    fn escapes(){
+        assert!(is_match("\\*.rs","*.rs"));
+        assert!(!is_match("\\*.rs","a.rs"));
+        assert!(is_match("a\\{b,c\\}","a{b,c}"));
+        assert!(is_match("\\[a]","[a]"));
+    }
+
+    #[test]
+    fn errors(){
+        for (glob,kind,span) in [
+            ("*.{rs,md",ErrorKind::UnclosedBrace,2..3),
+            ("a[bc",ErrorKind::UnclosedClass,1..2),
+            ("[]",ErrorKind::UnclosedClass,0..1),
+            ("[z-a]",ErrorKind::InvalidClassRange,1..4),
+            ("a\\",ErrorKind::EmptyEscape,1..2),
+        ]{
+            let error=parse(glob).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use std::{ops::Range, rc::Rc};
+
+use super::automaton::{Backtracker,DFA,NFA};
+use super::simplify::simplify;
+use super::transform::{parse_with_flags,Dialect,Flags};
+use super::{Error,ErrorKind};
+
+/*
+A compiled pattern, is_match runs the DFA and find runs the NFA
+since only the NFA knows which match has priority.
+Patterns with backreferences, look-arounds or atomic groups run on the backtracker instead, which gives up after its step limit,
+is_match, find and captures then report no match and the try_ versions return an error.
+POSIX patterns find the leftmost-longest match instead
+*/
+#[derive(Debug)]
+pub struct Regex{
+    pattern:String,
+    engine:Engine,
+    /*name of every group by index, group 0 is the whole match*/
+    names:Rc<Vec<Option<String>>>
+}
+
+/*
+Sets the flags a pattern starts with, inline flags can still change them
+*/
+#[derive(Clone,Debug)]
+pub struct RegexBuilder{
+    pattern:String,
+    flags:Flags,
+    backtrack_limit:usize
+}
+
+#[derive(Debug)]
+enum Engine{
+    Automata{nfa:NFA,dfa:DFA,longest:bool},
+    Backtrack(Backtracker)
+}
+
+/*
+Where each group of a match ended up, slots 2i and 2i+1 hold the span of group i
+and stay empty for groups that took no part in the match
+*/
+#[derive(Clone,Debug)]
+pub struct Captures<'h>{
+    haystack:&'h str,
+    slots:Vec<Option<usize>>,
+    names:Rc<Vec<Option<String>>>
+}
+
+#[derive(Clone,Copy,Debug,PartialEq, Eq)]
+pub struct Match<'h>{
+    haystack:&'h str,
+    start:usize,
+    end:usize
+}
+
+impl Regex {
+    pub fn new(pattern:&str)->Result<Regex,Error>{
+        RegexBuilder::new(pattern).build()
+    }
+
+    pub fn as_str(&self)->&str{
+        &self.pattern
+    }
+
+    pub fn is_match(&self,haystack:&str)->bool{
+        match &self.engine {
+            Engine::Automata{dfa,..}=>dfa.match_input(haystack),
+            Engine::Backtrack(_)=>self.find(haystack).is_some()
+        }
+    }
+
+    /*leftmost match, among matches starting there the one the pattern prefers or for POSIX the longest*/
+    pub fn find<'h>(&self,haystack:&'h str)->Option<Match<'h>>{
+        self.try_find(haystack).ok().flatten()
+    }
+
+    pub fn try_find<'h>(&self,haystack:&'h str)->Result<Option<Match<'h>>,Error>{
+        let range=match &self.engine {
+            Engine::Automata{nfa,longest:true,..}=>nfa.find_longest(haystack),
+            Engine::Automata{nfa,..}=>nfa.find(haystack),
+            Engine::Backtrack(backtracker)=>backtracker.find(haystack).map_err(|_| self.limit_exceeded())?
+        };
+        Ok(range.map(|range| Match{haystack,start:range.start,end:range.end}))
+    }
+
+    /*same match as find along with the spans of its groups*/
+    pub fn captures<'h>(&self,haystack:&'h str)->Option<Captures<'h>>{
+        self.try_captures(haystack).ok().flatten()
+    }
+
+    pub fn try_captures<'h>(&self,haystack:&'h str)->Result<Option<Captures<'h>>,Error>{
+        let slots=match &self.engine {
+            Engine::Automata{nfa,longest:true,..}=>nfa.captures_longest(haystack),
+            Engine::Automata{nfa,..}=>nfa.captures(haystack),
+            Engine::Backtrack(backtracker)=>backtracker.captures(haystack).map_err(|_| self.limit_exceeded())?
+        };
+        Ok(slots.map(|mut slots|{
+            slots.resize(2*self.names.len(), None);
+            Captures{haystack,slots,names:self.names.clone()}
+        }))
+    }
+
+    fn limit_exceeded(&self)->Error{
+        Error::new(ErrorKind::BacktrackLimitExceeded, &self.pattern, 0..self.pattern.len())
+    }
+
+    /*number of groups including group 0*/
+    pub fn captures_len(&self)->usize{
+        self.names.len()
+    }
+}
+
+impl RegexBuilder {
+    pub fn new(pattern:&str)->RegexBuilder{
+        RegexBuilder{
+            pattern:pattern.to_string(),
+            flags:Flags::default(),
+            backtrack_limit:Backtracker::DEFAULT_LIMIT
+        }
+    }
+
+    pub fn case_insensitive(&mut self,yes:bool)->&mut RegexBuilder{
+        self.flags.case_insensitive=yes;
+        self
+    }
+
+    pub fn multi_line(&mut self,yes:bool)->&mut RegexBuilder{
+        self.flags.multi_line=yes;
+        self
+    }
+
+    pub fn dot_matches_new_line(&mut self,yes:bool)->&mut RegexBuilder{
+        self.flags.dot_matches_new_line=yes;
+        self
+    }
+
+    pub fn ignore_whitespace(&mut self,yes:bool)->&mut RegexBuilder{
+        self.flags.ignore_whitespace=yes;
+        self
+    }
+
+    pub fn unicode(&mut self,yes:bool)->&mut RegexBuilder{
+        self.flags.unicode=yes;
+        self
+    }
+
+    pub fn expansion_limit(&mut self,limit:usize)->&mut RegexBuilder{
+        self.flags.expansion_limit=limit;
+        self
+    }
+
+    pub fn dialect(&mut self,dialect:Dialect)->&mut RegexBuilder{
+        self.flags.dialect=dialect;
+        self
+    }
+
+    /*steps a backtracking search may take before giving up*/
+    pub fn backtrack_limit(&mut self,limit:usize)->&mut RegexBuilder{
+        self.backtrack_limit=limit;
+        self
+    }
+
+    pub fn build(&self)->Result<Regex,Error>{
+        let ast=parse_with_flags(&self.pattern, self.flags)?;
+        let engine=if ast.needs_backtracking() {
+            Engine::Backtrack(Backtracker::from_ast(&ast, self.backtrack_limit))
+        }
+        else {
+            let nfa=NFA::from_ast(&simplify(ast.clone()));
+            let dfa=DFA::from_nfa(&nfa);
+            Engine::Automata{nfa,dfa,longest:self.flags.dialect==Dialect::Posix}
+        };
+        let names=Rc::new(ast.capture_names());
+        Ok(Regex{pattern:self.pattern.clone(),engine,names})
+    }
+}
+
+impl<'h> Captures<'h> {
+    pub fn get(&self,index:usize)->Option<Match<'h>>{
+        let start=(*self.slots.get(2*index)?)?;
+        let end=(*self.slots.get(2*index+1)?)?;
+        Some(Match{haystack:self.haystack,start,end})
+    }
+
+    pub fn name(&self,name:&str)->Option<Match<'h>>{
+        let index=self.names.iter().position(|group| group.as_deref()==Some(name))?;
+        self.get(index)
+    }
+
+    pub fn len(&self)->usize{
+        self.names.len()
+    }
+
+    /*never empty, group 0 is always there*/
+    pub fn is_empty(&self)->bool{
+        self.names.is_empty()
+    }
+}
+
+impl<'h> Match<'h> {
+    /*byte offset into the haystack*/
+    pub fn start(&self)->usize{
+        self.start
+    }
+
+    pub fn end(&self)->usize{
+        self.end
+    }
+
+    pub fn range(&self)->Range<usize>{
+        self.start..self.end
+    }
+
+    pub fn as_str(&self)->&'h str{
+        &self.haystack[self.start..self.end]
+    }
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn find_match(){
+        let regex=Regex::new("[0-9]+").unwrap();
+        let found=regex.find("abc 123 456").unwrap();
+
+        assert_eq!(found.range(),4..7);
+        assert_eq!(found.as_str(),"123");
+        assert!(regex.is_match("x9"));
+        assert!(regex.find("abc").is_none());
+    }
+
+    #[test]
+    fn builder_case_insensitive(){
+        let regex=RegexBuilder::new("error: [a-c]+").case_insensitive(true).build().unwrap();
+
+        assert_eq!(regex.find("ERROR: CaB!").map(|found| found.as_str()),Some("ERROR: CaB"));
+        assert!(regex.is_match("Error: b"));
+        assert!(!regex.is_match("Error: d"));
+        assert_eq!(regex.as_str(),"error: [a-c]+");
+    }
+
+    #[test]
+    fn builder_flags(){
+        let regex=RegexBuilder::new("^ a . b $ # one line").multi_line(true).dot_matches_new_line(true).ignore_whitespace(true).build().unwrap();
+
+        assert_eq!(regex.find("x\na\nb\ny").map(|found| found.range()),Some(2..5));
+        assert!(!Regex::new("a.b").unwrap().is_match("a\nb"));
+    }
+
+    #[test]
+    fn captures_log_line(){
+        let regex=Regex::new("(?P<level>[A-Z]+) \\[(?<module>\\w+)(?::(\\d+))?\\] (.*)").unwrap();
+        let caps=regex.captures("12:00 WARN [net:80] retrying").unwrap();
+
+        assert_eq!(caps.len(),5);
+        assert_eq!(regex.captures_len(),5);
+        assert_eq!(caps.get(0).map(|found| found.as_str()),Some("WARN [net:80] retrying"));
+        assert_eq!(caps.name("level").map(|found| found.as_str()),Some("WARN"));
+        assert_eq!(caps.name("module").map(|found| found.range()),Some(12..15));
+        assert_eq!(caps.get(3).map(|found| found.as_str()),Some("80"));
+        assert_eq!(caps.get(4).map(|found| found.as_str()),Some("retrying"));
+        assert!(caps.get(5).is_none());
+        assert!(caps.name("missing").is_none());
+
+        let caps=regex.captures("INFO [db] up").unwrap();
+        assert!(caps.get(3).is_none());
+        assert_eq!(caps.name("module").map(|found| found.as_str()),Some("db"));
+        assert!(regex.captures("no match").is_none());
+    }
+
+    #[test]
+    fn backreference_repeated_word(){
+        let regex=Regex::new("\\b(?<word>\\w+)\\s+\\k<word>\\b").unwrap();
+        let caps=regex.captures("it was the the best").unwrap();
+
+        assert_eq!(caps.get(0).map(|found| found.as_str()),Some("the the"));
+        assert_eq!(caps.name("word").map(|found| found.range()),Some(7..10));
+        assert!(regex.is_match("so so"));
+        assert!(!regex.is_match("the then"));
+    }
+
+    #[test]
+    fn backtrack_limit(){
+        let haystack="a".repeat(25);
+        let regex=RegexBuilder::new("(a|aa)*\\1b").backtrack_limit(10_000).build().unwrap();
+
+        assert_eq!(regex.try_find(&haystack).unwrap_err().kind(),&ErrorKind::BacktrackLimitExceeded);
+        assert!(regex.find(&haystack).is_none());
+        assert!(!regex.is_match(&haystack));
+        assert_eq!(regex.try_find("aab").map(|found| found.map(|found| found.range())),Ok(Some(0..3)));
+    }
+
+    /*an empty look-ahead sends the pattern to the backtracker without changing what it matches*/
+    #[test]
+    fn same_matches_on_either_engine(){
+        for (pattern,haystack) in [("(?:a*?)+","aaa"),("(?:(a)*?)+","aaa"),("b(?:()?)+","b"),("(?:a?|b)*","ab")] {
+            let automata=Regex::new(pattern).unwrap();
+            let backtracking=Regex::new(&format!("{pattern}(?=)")).unwrap();
+            let ranges=|regex:&Regex| regex.captures(haystack).map(|caps| (0..caps.len()).map(|i| caps.get(i).map(|found| found.range())).collect::<Vec<_>>());
+
+            assert_eq!(ranges(&automata),ranges(&backtracking),"{pattern}");
+        }
+    }
+
+    #[test]
+    fn look_behind_price(){
+        let regex=Regex::new("(?<![-\\d.])\\d+\\.\\d{2}\\b").unwrap();
+
+        assert_eq!(regex.find("refund -12.50, charge 30.00").map(|found| found.as_str()),Some("30.00"));
+        assert!(!regex.is_match("total -7.25"));
+    }
+
+    #[test]
+    fn posix_leftmost_longest(){
+        let regex=RegexBuilder::new("(a|ab)(c|bcd)(d*)").dialect(Dialect::Posix).build().unwrap();
+        let caps=regex.captures("abcd").unwrap();
+
+        assert_eq!(caps.get(0).map(|found| found.as_str()),Some("abcd"));
+        assert_eq!(Regex::new("(a|ab)(c|bcd)(d*)").unwrap().find("abcd").map(|found| found.as_str()),Some("abcd"));
+        assert_eq!(Regex::new("int|integer").unwrap().find("integer").map(|found| found.as_str()),Some("int"));
+
+        let regex=RegexBuilder::new("int|integer").dialect(Dialect::Posix).build().unwrap();
+        assert_eq!(regex.find("an integer").map(|found| found.range()),Some(3..10));
+        assert!(RegexBuilder::new("\\d+").dialect(Dialect::Posix).build().is_err());
+    }
+
+    #[test]
+    fn ecmascript_pattern(){
+        let regex=RegexBuilder::new("^\\d{3}-[^]{2}$").dialect(Dialect::EcmaScript).build().unwrap();
+
+        assert!(regex.is_match("123-\n\n"));
+        assert!(!regex.is_match("١٢٣-ab"));
+        assert!(RegexBuilder::new("\\d{3}").build().unwrap().is_match("١٢٣"));
+        assert!(RegexBuilder::new("(?i)a").dialect(Dialect::EcmaScript).build().is_err());
+    }
+}
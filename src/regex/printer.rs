@@ -0,0 +1,414 @@
+use super::ast::{Ast,Class,ClassSet,Look,LookAroundKind,PerlClassKind,Repetition,RepetitionKind,UnicodeProperty};
+
+/*
+Writes an ast back out in the native dialect so that parsing the result gives the same ast.
+
+Only chars that would mean something else are escaped, \.+*?()|[{^$ outside brackets and \[]- inside them
+along with a leading '^', control chars are written as \n \t \r or \x{..}.
+Parentheses only go where the ast nests something the syntax can't show otherwise:
+a concat or alternation inside another one or under a quantifier, an empty item in a concat
+and a greedy repetition under '?' or '+' which would read as lazy or possessive.
+Flags that only exist while parsing come back as scoped groups, (?s:.) (?m:^) (?-u:\d) (?i:\1).
+*/
+pub fn print(ast:&Ast)->String{
+    let mut out=String::new();
+    print_ast(ast, &mut out);
+    out
+}
+
+fn print_ast(ast:&Ast,out:&mut String){
+    match ast {
+        Ast::Empty=>{},
+        Ast::Literal(c)=>print_char(*c, "\\.+*?()|[{^$", out),
+        Ast::Class(class)=>print_class(class, out),
+        Ast::Look(look)=>out.push_str(match look {
+            Look::Start=>"^",
+            Look::End=>"$",
+            Look::StartLine=>"(?m:^)",
+            Look::EndLine=>"(?m:$)",
+            Look::WordBoundaryUnicode=>"\\b",
+            Look::NotWordBoundaryUnicode=>"\\B",
+            Look::WordBoundaryAscii=>"(?-u:\\b)",
+            Look::NotWordBoundaryAscii=>"(?-u:\\B)",
+        }),
+        Ast::Concat(items)=>print_concat(items, out),
+        Ast::Alternation(items)=>{
+            for (i,item) in items.iter().enumerate(){
+                if i>0 {
+                    out.push('|');
+                }
+                match item {
+                    Ast::Alternation(_)=>print_non_capturing(item, out),
+                    _=>print_ast(item, out)
+                }
+            }
+        },
+        Ast::Repetition(repetition)=>print_repetition(repetition, out),
+        Ast::Group(group)=>{
+            out.push('(');
+            if let Some(name)=&group.name {
+                out.push_str("?<");
+                out.push_str(name);
+                out.push('>');
+            }
+            print_ast(&group.ast, out);
+            out.push(')');
+        },
+        Ast::Backreference(backreference)=>{
+            let index=backreference.index;
+            match backreference.case_insensitive {
+                true=>out.push_str(&format!("(?i:\\{index})")),
+                false=>out.push_str(&format!("\\{index}"))
+            }
+        },
+        Ast::LookAround(look_around)=>{
+            out.push_str(match look_around.kind {
+                LookAroundKind::Ahead=>"(?=",
+                LookAroundKind::NotAhead=>"(?!",
+                LookAroundKind::Behind=>"(?<=",
+                LookAroundKind::NotBehind=>"(?<!",
+            });
+            print_ast(&look_around.ast, out);
+            out.push(')');
+        },
+        Ast::Atomic(ast)=>match ast.as_ref() {
+            Ast::Repetition(repetition) if repetition.greedy=>{
+                print_repetition(repetition, out);
+                out.push('+');
+            }
+            _=>{
+                out.push_str("(?>");
+                print_ast(ast, out);
+                out.push(')');
+            }
+        },
+    }
+}
+
+/*a backreference can't run into a digit that follows it, \1 then 0 would read as \10*/
+fn print_concat(items:&[Ast],out:&mut String){
+    let printed:Vec<String>=items
+    .iter()
+    .map(|item|{
+        let mut out=String::new();
+        match item {
+            Ast::Empty|Ast::Concat(_)|Ast::Alternation(_)=>print_non_capturing(item, &mut out),
+            _=>print_ast(item, &mut out)
+        }
+        out
+    })
+    .collect();
+
+    for (i,item) in printed.iter().enumerate(){
+        let before_digit=printed.get(i+1).is_some_and(|next| next.starts_with(|c:char| c.is_ascii_digit()));
+        match &items[i] {
+            Ast::Backreference(_) if before_digit=>{
+                out.push_str("(?:");
+                out.push_str(item);
+                out.push(')');
+            }
+            _=>out.push_str(item)
+        }
+    }
+}
+
+fn print_repetition(repetition:&Repetition,out:&mut String){
+    let (min,max)=repetition.kind.bounds();
+    let reads_as_suffix=matches!(repetition.kind,RepetitionKind::ZeroOrOne|RepetitionKind::OneOrMore);
+    match repetition.ast.as_ref() {
+        Ast::Empty|Ast::Concat(_)|Ast::Alternation(_)=>print_non_capturing(&repetition.ast, out),
+        Ast::Repetition(inner) if inner.greedy&&reads_as_suffix=>print_non_capturing(&repetition.ast, out),
+        ast=>print_ast(ast, out)
+    }
+
+    match repetition.kind {
+        RepetitionKind::ZeroOrMore=>out.push('*'),
+        RepetitionKind::OneOrMore=>out.push('+'),
+        RepetitionKind::ZeroOrOne=>out.push('?'),
+        RepetitionKind::Exactly(_)=>out.push_str(&format!("{{{min}}}")),
+        RepetitionKind::AtLeast(_)=>out.push_str(&format!("{{{min},}}")),
+        RepetitionKind::Bounded(..)=>out.push_str(&format!("{{{min},{}}}",max.expect("bounded repetition"))),
+    }
+    if !repetition.greedy {
+        out.push('?');
+    }
+}
+
+fn print_non_capturing(ast:&Ast,out:&mut String){
+    out.push_str("(?:");
+    print_ast(ast, out);
+    out.push(')');
+}
+
+fn print_class(class:&Class,out:&mut String){
+    match class {
+        Class::All=>out.push_str("(?s:.)"),
+        Class::AllExceptNewline=>out.push('.'),
+        Class::Set(set)=>print_set(set, out),
+        Class::Perl(perl)=>{
+            let letter=match perl.kind {
+                PerlClassKind::Digit=>'d',
+                PerlClassKind::Word=>'w',
+                PerlClassKind::Space=>'s',
+            };
+            let letter=if perl.negated {letter.to_ascii_uppercase()} else {letter};
+            match perl.unicode {
+                true=>out.push_str(&format!("\\{letter}")),
+                false=>out.push_str(&format!("(?-u:\\{letter})"))
+            }
+        },
+        Class::Unicode(unicode)=>{
+            out.push_str(if unicode.negated {"\\P"} else {"\\p"});
+            let name=unicode.property.name();
+            /*a name that's both a category and a script needs the key to come back as the same property*/
+            let name=match unicode.property {
+                _ if UnicodeProperty::from_name(name)==Some(unicode.property)=>name.to_string(),
+                UnicodeProperty::GeneralCategory(_)=>format!("gc={name}"),
+                UnicodeProperty::Script(_)=>format!("sc={name}"),
+            };
+            out.push_str(&format!("{{{name}}}"));
+        },
+    }
+}
+
+/*
+negated when the complement takes fewer ranges, [] and [^] would not close
+so the empty set is the negated full set and the full set stays positive,
+ranges of one or two chars are written out
+*/
+fn print_set(set:&ClassSet,out:&mut String){
+    let mut complement=set.clone();
+    complement.negate();
+    let negated=set.is_empty()||!complement.is_empty()&&complement.ranges().len()<set.ranges().len();
+    let ranges=if negated {complement.ranges()} else {set.ranges()};
+
+    out.push('[');
+    if negated {
+        out.push('^');
+    }
+    let start=out.len();
+    for range in ranges {
+        let special=if out.len()==start&&!negated {"\\[]-^"} else {"\\[]-"};
+        print_char(range.start, special, out);
+        if range.end==range.start {
+            continue;
+        }
+        if char::from_u32(range.start as u32+1)!=Some(range.end) {
+            out.push('-');
+        }
+        print_char(range.end, "\\[]-", out);
+    }
+    out.push(']');
+}
+
+fn print_char(c:char,special:&str,out:&mut String){
+    match c {
+        '\n'=>out.push_str("\\n"),
+        '\t'=>out.push_str("\\t"),
+        '\r'=>out.push_str("\\r"),
+        _ if c.is_control()=>out.push_str(&format!("\\x{{{:X}}}",c as u32)),
+        _ if special.contains(c)=>{
+            out.push('\\');
+            out.push(c);
+        }
+        _=>out.push(c)
+    }
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::regex::ast::{Backreference,ClassRange,LookAround,PerlClass,UnicodeClass};
+    use crate::regex::transform::{expanded_size,parse,Flags};
+
+    fn round_trip(pattern:&str)->String{
+        print(&parse(pattern).unwrap())
+    }
+
+    #[test]
+    fn canonical_patterns(){
+        for (pattern,expect) in [
+            ("a\\.b\\*","a\\.b\\*"),
+            ("\\]\\}\\-\\&","]}-&"),
+            ("(?:ab)c|(?:d|e)","(?:ab)c|(?:d|e)"),
+            ("(?:a)(?:b)","ab"),
+            ("(ab)*(?:a|b)+?","(ab)*(?:a|b)+?"),
+            ("a{2}+|a{2}*|(?:a{2})+","a{2}+|a{2}*|(?:a{2})+"),
+            ("(?>a*)(?>a*?)","a*+(?>a*?)"),
+            ("(?P<x>a)\\k<x>(?:\\1)0\\1","(?<x>a)\\1(?:\\1)0\\1"),
+            ("\\A\\z(?m)^$","^$(?m:^)(?m:$)"),
+            ("(?s).(?-s).","(?s:.)."),
+            ("(?i)[a-c][0-9x]\\x{0}\\n","[A-Ca-c][0-9Xx]\\x{0}\\n"),
+            ("[^a][^]-][\\^a][a^][^^]","[^a][^\\-\\]][\\^a][\\^a][^^]"),
+            ("[ab][a-c][\\[-\\]]","[ab][a-c][\\[-\\]]"),
+            ("(?-u:\\d\\b)\\W\\pL\\P{Greek}","(?:(?-u:\\d)(?-u:\\b))\\W\\p{Letter}\\P{Greek}"),
+            ("(a)(?i)\\1?","(a)(?i:\\1)?"),
+            ("(?=a)(?!b)(?<=c)(?<!d)","(?=a)(?!b)(?<=c)(?<!d)"),
+            ("a|","a|"),
+            ("(?:)*(?:)a","(?:)*(?:)a"),
+        ]{
+            assert_eq!(round_trip(pattern),expect,"{pattern}");
+        }
+    }
+
+    #[test]
+    fn round_trips_special_sets(){
+        let full=Ast::Class(Class::Set(ClassSet::full()));
+        let empty=Ast::Class(Class::Set(ClassSet::empty()));
+        let unicode=Ast::Class(Class::Unicode(UnicodeClass::new(UnicodeProperty::from_name("Lu").unwrap(), false)));
+        for ast in [full,empty,unicode,Ast::Empty] {
+            assert_eq!(parse(&print(&ast)).unwrap(),ast);
+        }
+    }
+
+    /*xorshift, enough to spread the generated asts without pulling in a crate*/
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self)->u64{
+            self.0^=self.0<<13;
+            self.0^=self.0>>7;
+            self.0^=self.0<<17;
+            self.0
+        }
+
+        fn below(&mut self,n:usize)->usize{
+            (self.next()%n as u64) as usize
+        }
+
+        fn chance(&mut self,percent:usize)->bool{
+            self.below(100)<percent
+        }
+    }
+
+    /*
+    only asts the parser can produce, groups are numbered in the order they open,
+    backreferences point at opened groups and look-behinds have a bounded length
+    */
+    struct Generator{
+        rng:Rng,
+        captures:usize
+    }
+
+    impl Generator {
+        fn char(&mut self)->char{
+            const CHARS:&[char]=&['a','b','0','1','\\','.','*','+','?','(',')','|','[',']','{','}','^','$','-','&','~','#',' ','\n','\0','é','日','\u{10FFFF}'];
+            CHARS[self.rng.below(CHARS.len())]
+        }
+
+        fn set(&mut self)->ClassSet{
+            let mut set=ClassSet::empty();
+            for _ in 0..self.rng.below(4)+1 {
+                let (a,b)=(self.char(),self.char());
+                set.push(ClassRange::new(a.min(b), a.max(b)));
+            }
+            if self.rng.chance(30) {
+                set.negate();
+            }
+            set
+        }
+
+        fn class(&mut self)->Class{
+            match self.rng.below(6) {
+                0=>Class::All,
+                1=>Class::AllExceptNewline,
+                2=>{
+                    let kind=[PerlClassKind::Digit,PerlClassKind::Word,PerlClassKind::Space][self.rng.below(3)];
+                    Class::Perl(PerlClass::new(kind, self.rng.chance(50), self.rng.chance(50)))
+                }
+                3=>{
+                    let name=["Lu","L","Greek","Han","Nd"][self.rng.below(5)];
+                    Class::Unicode(UnicodeClass::new(UnicodeProperty::from_name(name).unwrap(), self.rng.chance(50)))
+                }
+                _=>Class::Set(self.set())
+            }
+        }
+
+        fn look(&mut self)->Look{
+            [
+                Look::Start,Look::End,Look::StartLine,Look::EndLine,
+                Look::WordBoundaryUnicode,Look::NotWordBoundaryUnicode,Look::WordBoundaryAscii,Look::NotWordBoundaryAscii
+            ][self.rng.below(8)]
+        }
+
+        fn repetition_kind(&mut self)->RepetitionKind{
+            let (n,m)=(self.rng.below(3) as u32,self.rng.below(3) as u32);
+            match self.rng.below(6) {
+                0=>RepetitionKind::ZeroOrMore,
+                1=>RepetitionKind::OneOrMore,
+                2=>RepetitionKind::ZeroOrOne,
+                3=>RepetitionKind::Exactly(n),
+                4=>RepetitionKind::AtLeast(n),
+                _=>RepetitionKind::Bounded(n.min(m), n.max(m))
+            }
+        }
+
+        fn items(&mut self,depth:usize)->Vec<Ast>{
+            (0..self.rng.below(3)+2).map(|_| self.ast(depth-1)).collect()
+        }
+
+        fn ast(&mut self,depth:usize)->Ast{
+            let leaf=depth==0||self.rng.chance(30);
+            match self.rng.below(if leaf {5} else {12}) {
+                0=>Ast::Empty,
+                1=>Ast::Literal(self.char()),
+                2=>Ast::Class(self.class()),
+                3=>Ast::Look(self.look()),
+                4=>match self.captures {
+                    0=>Ast::Literal(self.char()),
+                    captures=>{
+                        let index=self.rng.below(captures)+1;
+                        Ast::Backreference(Backreference{index,case_insensitive:self.rng.chance(30)})
+                    }
+                },
+                5|6=>Ast::Concat(self.items(depth)),
+                7=>Ast::Alternation(self.items(depth)),
+                8=>{
+                    let kind=self.repetition_kind();
+                    let ast=self.ast(depth-1);
+                    match self.rng.chance(30) {
+                        true=>Ast::lazy_repetition(kind, ast),
+                        false=>Ast::repetition(kind, ast)
+                    }
+                }
+                9=>{
+                    self.captures+=1;
+                    let index=self.captures;
+                    let ast=self.ast(depth-1);
+                    match self.rng.chance(50) {
+                        true=>Ast::named_group(index, &format!("g{index}"), ast),
+                        false=>Ast::group(index, ast)
+                    }
+                }
+                10=>{
+                    let kind=[LookAroundKind::Ahead,LookAroundKind::NotAhead,LookAroundKind::Behind,LookAroundKind::NotBehind][self.rng.below(4)];
+                    let captures=self.captures;
+                    let mut ast=self.ast(depth-1);
+                    let behind=matches!(kind,LookAroundKind::Behind|LookAroundKind::NotBehind);
+                    if behind&&ast.length_bounds().1.is_none() {
+                        self.captures=captures;
+                        ast=Ast::Literal(self.char());
+                    }
+                    Ast::LookAround(LookAround{kind,ast:Box::new(ast)})
+                }
+                _=>Ast::atomic(self.ast(depth-1))
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_random_asts(){
+        let mut generator=Generator{rng:Rng(0x9E37_79B9_7F4A_7C15),captures:0};
+        for _ in 0..2000 {
+            generator.captures=0;
+            let ast=generator.ast(5);
+            if expanded_size(&ast)>Flags::default().expansion_limit {
+                continue;
+            }
+            let printed=print(&ast);
+            assert_eq!(parse(&printed).as_ref(),Ok(&ast),"{printed}");
+            assert_eq!(print(&parse(&printed).unwrap()),printed);
+        }
+    }
+}
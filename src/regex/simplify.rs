@@ -0,0 +1,264 @@
+use std::convert::Infallible;
+
+use super::ast::{Ast,Class,ClassSet,Repetition,RepetitionKind};
+use super::visit::{fold,Fold};
+
+/*
+Rewrites an ast into a smaller one that matches the same text with the same priorities,
+so leftmost-first searches find the same matches and groups capture the same spans.
+Runs bottom up, every node is rewritten after its children:
+
+concat       nested concats are spliced in and empty items dropped, (?:ab)(?:)c is abc
+repetition   {0,} {1,} {0,1} become * + ?, x{1} is x, x{0} is empty unless x has groups,
+             directly nested greedy * + ? collapse into one when what they repeat has no groups and
+             can't match empty, (?:a*)+ is a*, (?:(a)?)+ and (?:(?:a?b?)*)+ stay as they are
+alternation  nested alternations are spliced in, an alternative equal to an earlier one is dropped
+             since it can only fail where that one failed, adjacent alternatives starting with the
+             same single chars share them, ab|ac|d is a(?:b|c)|d, and adjacent single char alternatives
+             become one class, a|[bc]|d is [a-d]. A shared prefix that could match in more than one way
+             would try every way of the first alternative before the second, a*ab|a* is not a*(?:ab|)
+
+Capture groups are never merged or removed, (ab)|(ac) stays as it is since both spans are observable.
+*/
+pub fn simplify(ast:Ast)->Ast{
+    match fold(ast, &mut Simplify) {
+        Ok(ast)=>ast,
+        Err(never)=>match never {}
+    }
+}
+
+struct Simplify;
+
+impl Fold for Simplify {
+    type Err=Infallible;
+
+    fn fold_post(&mut self,ast:Ast)->Result<Ast,Infallible>{
+        Ok(match ast {
+            Ast::Concat(items)=>Ast::concat(flatten_concat(items)),
+            Ast::Alternation(items)=>simplify_alternation(items),
+            Ast::Repetition(repetition)=>simplify_repetition(repetition),
+            _=>ast
+        })
+    }
+}
+
+fn flatten_concat(items:Vec<Ast>)->Vec<Ast>{
+    items
+    .into_iter()
+    .flat_map(|item|{
+        match item {
+            Ast::Concat(items)=>items,
+            Ast::Empty=>Vec::new(),
+            _=>vec![item]
+        }
+    })
+    .collect()
+}
+
+/*the items an alternative matches one after the other*/
+fn sequence(ast:Ast)->Vec<Ast>{
+    match ast {
+        Ast::Concat(items)=>items,
+        Ast::Empty=>Vec::new(),
+        _=>vec![ast]
+    }
+}
+
+fn simplify_alternation(items:Vec<Ast>)->Ast{
+    let mut alternatives:Vec<Ast>=Vec::new();
+    for item in items.into_iter().flat_map(|item| match item {
+        Ast::Alternation(items)=>items,
+        _=>vec![item]
+    }){
+        if !alternatives.contains(&item) {
+            alternatives.push(item);
+        }
+    }
+
+    Ast::alternation(merge_classes(factor_prefixes(alternatives)))
+}
+
+/*
+a run of adjacent alternatives with the same first char becomes that run's common prefix of chars
+followed by the alternation of what is left of each, which is simplified in turn
+*/
+fn factor_prefixes(alternatives:Vec<Ast>)->Vec<Ast>{
+    let mut sequences:Vec<Vec<Ast>>=alternatives.into_iter().map(sequence).collect();
+    let mut factored=Vec::new();
+
+    while !sequences.is_empty() {
+        let run=sequences
+        .iter()
+        .take_while(|sequence| sequence.first().is_some_and(|first| single_char_set(first).is_some()&&sequences[0].first()==Some(first)))
+        .count()
+        .max(1);
+        let mut run:Vec<Vec<Ast>>=sequences.drain(..run).collect();
+        if run.len()==1 {
+            factored.push(Ast::concat(run.pop().expect("run of one")));
+            continue;
+        }
+
+        let prefix_len=run
+        .iter()
+        .map(|sequence| sequence.iter().zip(&run[0]).take_while(|(item,first)| item==first&&single_char_set(item).is_some()).count())
+        .min()
+        .expect("run of two or more");
+        let mut prefix:Vec<Ast>=run[0][..prefix_len].to_vec();
+        let suffixes=run
+        .into_iter()
+        .map(|mut sequence| Ast::concat(sequence.split_off(prefix_len)))
+        .collect();
+        prefix.extend(sequence(simplify_alternation(suffixes)));
+        factored.push(Ast::concat(prefix));
+    }
+
+    factored
+}
+
+/*adjacent alternatives that each match a single char, merging them keeps the order the others are tried in*/
+fn merge_classes(alternatives:Vec<Ast>)->Vec<Ast>{
+    let mut merged:Vec<Ast>=Vec::new();
+    let mut run:Vec<Ast>=Vec::new();
+    let end_run=|run:&mut Vec<Ast>,merged:&mut Vec<Ast>|{
+        match run.len() {
+            0=>{},
+            1=>merged.append(run),
+            _=>{
+                let mut set=ClassSet::empty();
+                for ast in run.drain(..) {
+                    set.union(&single_char_set(&ast).expect("run only holds single chars"));
+                }
+                merged.push(Ast::Class(Class::Set(set)));
+            }
+        }
+    };
+
+    for alternative in alternatives {
+        match single_char_set(&alternative) {
+            Some(_)=>run.push(alternative),
+            None=>{
+                end_run(&mut run, &mut merged);
+                merged.push(alternative);
+            }
+        }
+    }
+    end_run(&mut run, &mut merged);
+
+    merged
+}
+
+fn single_char_set(ast:&Ast)->Option<ClassSet>{
+    match ast {
+        Ast::Literal(c)=>Some(ClassSet::from_char(*c)),
+        Ast::Class(class)=>Some(class.to_set()),
+        _=>None
+    }
+}
+
+fn simplify_repetition(repetition:Repetition)->Ast{
+    let Repetition{kind,greedy,ast}=repetition;
+    let kind=match kind {
+        RepetitionKind::AtLeast(0)=>RepetitionKind::ZeroOrMore,
+        RepetitionKind::AtLeast(1)=>RepetitionKind::OneOrMore,
+        RepetitionKind::Bounded(0,1)=>RepetitionKind::ZeroOrOne,
+        RepetitionKind::Bounded(n,m) if n==m=>RepetitionKind::Exactly(n),
+        _=>kind
+    };
+
+    match (kind,*ast) {
+        (RepetitionKind::Exactly(1),ast)=>ast,
+        (_,Ast::Empty)=>Ast::Empty,
+        (RepetitionKind::Exactly(0),ast) if ast.capture_names().len()==1=>Ast::Empty,
+        (outer,Ast::Repetition(inner)) if greedy&&inner.greedy&&is_star_like(outer)&&is_star_like(inner.kind)&&is_solid(&inner.ast)=>{
+            let kind=match (outer,inner.kind) {
+                (RepetitionKind::OneOrMore,RepetitionKind::OneOrMore)=>RepetitionKind::OneOrMore,
+                (RepetitionKind::ZeroOrOne,RepetitionKind::ZeroOrOne)=>RepetitionKind::ZeroOrOne,
+                _=>RepetitionKind::ZeroOrMore
+            };
+            Ast::repetition(kind, *inner.ast)
+        }
+        (kind,ast)=>Ast::Repetition(Repetition{kind,greedy,ast:Box::new(ast)})
+    }
+}
+
+fn is_star_like(kind:RepetitionKind)->bool{
+    matches!(kind,RepetitionKind::ZeroOrMore|RepetitionKind::OneOrMore|RepetitionKind::ZeroOrOne)
+}
+
+/*every pass through it consumes something and captures nothing, so how passes are grouped can't show*/
+fn is_solid(ast:&Ast)->bool{
+    ast.capture_names().len()==1&&ast.length_bounds().0>0
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::regex::automaton::NFA;
+    use crate::regex::printer::print;
+    use crate::regex::transform::parse;
+
+    fn simplified(pattern:&str)->String{
+        print(&simplify(parse(pattern).unwrap()))
+    }
+
+    #[test]
+    fn rewrites(){
+        for (pattern,expect) in [
+            ("(?:a*)*","a*"),
+            ("(?:a+)+|(?:b?)?|(?:c+)?|(?:d*?)*","a+|b?|c*|d*?*"),
+            ("(a*)*","(a*)*"),
+            ("(?:(a)?)+|(?:a?)*","(?:(a)?)+|a*"),
+            ("(?:(?:a?b?)*)+","(?:(?:a?b?)*)+"),
+            ("x{1}y{1,1}z{0,}w{1,}v{0,1}","xyz*w+v?"),
+            ("x{0}(y){0}","(y){0}"),
+            ("a|a|b|a","[ab]"),
+            ("(?:ab)|(?:ac)","a[bc]"),
+            ("(ab)|(ac)","(ab)|(ac)"),
+            ("abc|abd|ab|x","ab(?:[cd]|)|x"),
+            ("foo|bar|foot","foo|bar|foot"),
+            ("a*ab|a*","a*ab|a*"),
+            ("a+b|a+c","a+b|a+c"),
+            ("a|[bc]|d|ef|g|[0-9]","[a-d]|ef|[0-9g]"),
+            ("(?:(?:a)(?:b))(?:)c","abc"),
+            ("(?:a|(?:b|c))|d","[a-d]"),
+        ]{
+            assert_eq!(simplified(pattern),expect,"{pattern}");
+        }
+    }
+
+    #[test]
+    fn keeps_matches(){
+        for (pattern,haystack) in [
+            ("ab|a","xab"),
+            ("a|ab","xab"),
+            ("(?:a|ab)(c|bcd)","abcd"),
+            ("(?:x*)*y|(?:x+)?z","xxz"),
+            ("(a)|(a)b","ab"),
+            ("(?:(a)*)*b","aab"),
+            ("a*ab|a*","aab"),
+            ("(?:x|xy)z|(?:x|xy)","xyz"),
+            ("a*?b|a*?","aab"),
+            ("b(?:()?)+","b"),
+            ("(?:(a)?)+","aa"),
+            ("(?:(a*)?)+","aa"),
+            ("(?:(?:([ab])*?)+)+","ba"),
+            ("(?:(?:a?b?)*)+","ab"),
+        ]{
+            let ast=parse(pattern).unwrap();
+            let before=NFA::from_ast(&ast).captures(haystack);
+            let after=NFA::from_ast(&simplify(ast)).captures(haystack);
+            assert_eq!(before,after,"{pattern}");
+        }
+    }
+
+    #[test]
+    fn shrinks_nfa(){
+        for pattern in ["(?:a*)*","error|errno|warning|warn","a|b|c|d|e|f","(?:x{1}){1}|x"] {
+            let ast=parse(pattern).unwrap();
+            let before=NFA::from_ast(&ast).state_count();
+            let after=NFA::construct_nfa(pattern).unwrap().state_count();
+            assert!(after<before,"{pattern}: {after} states, {before} before");
+        }
+    }
+}
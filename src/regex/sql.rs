@@ -0,0 +1,412 @@
+use super::ast::{Ast,Class,ClassRange,ClassSet,Look,RepetitionKind};
+use super::automaton::{DFA,NFA};
+use super::error::{Error,ErrorKind};
+use super::transform::{counted_kind,expanded_size,split_first,Flags};
+
+/*
+grammer:
+
+LIKE -> (WILDCARD | ESCAPE char | char)*
+WILDCARD -> '%' | '_'
+
+SIMILAR -> CONCAT ('|' CONCAT)*
+CONCAT -> REPETITION*
+REPETITION -> PRIMARY QUANTIFIER*
+QUANTIFIER -> '*' | '+' | '?' | '{' COUNT '}'
+PRIMARY -> '(' SIMILAR ')' | WILDCARD | BRACKET | ESCAPE char | char
+BRACKET -> '[' '^'? ITEM+ ']'
+ITEM -> '[:' name ':]' | ESCAPE? char ('-' ESCAPE? char)?
+
+'%' matches any run of chars and '_' any single char, newlines included.
+ESCAPE is the char given with the ESCAPE clause, without one nothing is escaped.
+Unlike a regex '.', '^' and '$' are plain chars and groups don't capture.
+Both dialects have to match the whole string.
+*/
+
+/*
+A LIKE or SIMILAR TO pattern compiled to a DFA
+*/
+#[derive(Debug)]
+pub struct SqlPattern{
+    pattern:String,
+    dfa:DFA
+}
+
+impl SqlPattern {
+    pub fn like(pattern:&str,escape:Option<char>)->Result<SqlPattern,Error>{
+        Ok(SqlPattern::from_ast(pattern, parse_like(pattern, escape)?))
+    }
+
+    pub fn similar_to(pattern:&str,escape:Option<char>)->Result<SqlPattern,Error>{
+        Ok(SqlPattern::from_ast(pattern, parse_similar_to(pattern, escape)?))
+    }
+
+    fn from_ast(pattern:&str,ast:Ast)->SqlPattern{
+        let dfa=DFA::from_nfa(&NFA::from_ast(&ast));
+        SqlPattern{pattern:pattern.to_string(),dfa}
+    }
+
+    pub fn as_str(&self)->&str{
+        &self.pattern
+    }
+
+    pub fn is_match(&self,input:&str)->bool{
+        self.dfa.match_input(input)
+    }
+}
+
+/*the ast of a LIKE pattern, anchored at both ends*/
+pub fn parse_like(pattern:&str,escape:Option<char>)->Result<Ast,Error>{
+    let parser=Parser{pattern,escape};
+    Ok(anchored(parser.parse_like(pattern)?))
+}
+
+/*the ast of a SIMILAR TO pattern, anchored at both ends*/
+pub fn parse_similar_to(pattern:&str,escape:Option<char>)->Result<Ast,Error>{
+    let parser=Parser{pattern,escape};
+    let (ast,remain)=parser.parse_similar(pattern)?;
+    if !remain.is_empty() {
+        return Err(parser.error(ErrorKind::UnopenedGroup, remain));
+    }
+
+    Ok(anchored(ast))
+}
+
+struct Parser<'a>{
+    pattern:&'a str,
+    escape:Option<char>
+}
+
+impl<'a> Parser<'a> {
+    fn offset(&self,at:&str)->usize{
+        self.pattern.len()-at.len()
+    }
+
+    /*points at the char starting at*/
+    fn error(&self,kind:ErrorKind,at:&str)->Error{
+        let start=self.offset(at);
+        let end=start+at.chars().next().map_or(0, char::len_utf8);
+        Error::new(kind, self.pattern, start..end)
+    }
+
+    /*points from the start of from up to the start of to*/
+    fn error_between(&self,kind:ErrorKind,from:&str,to:&str)->Error{
+        Error::new(kind, self.pattern, self.offset(from)..self.offset(to))
+    }
+
+    /*the char after the escape at s*/
+    fn parse_escaped(&self,s:&'a str,after:&'a str)->Result<(char,&'a str),Error>{
+        split_first(after).ok_or_else(|| self.error(ErrorKind::EmptyEscape, s))
+    }
+
+    /*
+    LIKE -> (WILDCARD | ESCAPE char | char)*
+    */
+    fn parse_like(&self,s:&'a str)->Result<Ast,Error>{
+        let mut items=Vec::new();
+        let mut remain=s;
+
+        while let Some((c,after))=split_first(remain){
+            let item;
+            (item,remain)=match c {
+                _ if Some(c)==self.escape=>{
+                    let (escaped,after)=self.parse_escaped(remain, after)?;
+                    (Ast::Literal(escaped),after)
+                }
+                '%'=>(any_string(),after),
+                '_'=>(Ast::Class(Class::All),after),
+                _=>(Ast::Literal(c),after)
+            };
+            items.push(item);
+        }
+
+        Ok(Ast::concat(items))
+    }
+
+    /*
+    SIMILAR -> CONCAT ('|' CONCAT)*
+    stops before an unmatched ')'
+    */
+    fn parse_similar(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let mut alternatives=Vec::new();
+        let mut remain=s;
+
+        loop {
+            let alternative;
+            (alternative,remain)=self.parse_concat(remain)?;
+            alternatives.push(alternative);
+            remain=match split_first(remain) {
+                Some(('|',after))=>after,
+                _=>break Ok((Ast::alternation(alternatives),remain))
+            };
+        }
+    }
+
+    /*
+    CONCAT -> REPETITION*
+    */
+    fn parse_concat(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let mut items=Vec::new();
+        let mut remain=s;
+
+        while let Some((c,_))=split_first(remain){
+            if matches!(c,'|'|')')&&Some(c)!=self.escape {
+                break;
+            }
+            let item;
+            (item,remain)=self.parse_repetition(remain)?;
+            items.push(item);
+        }
+
+        Ok((Ast::concat(items),remain))
+    }
+
+    /*
+    REPETITION -> PRIMARY QUANTIFIER*
+    */
+    fn parse_repetition(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (mut ast,mut remain)=self.parse_primary(s)?;
+
+        loop {
+            let kind;
+            (kind,remain)=match split_first(remain) {
+                Some((c,_)) if Some(c)==self.escape=>break,
+                Some(('*',after))=>(RepetitionKind::ZeroOrMore,after),
+                Some(('+',after))=>(RepetitionKind::OneOrMore,after),
+                Some(('?',after))=>(RepetitionKind::ZeroOrOne,after),
+                Some(('{',_))=>self.parse_counted(remain)?,
+                _=>break
+            };
+            ast=Ast::repetition(kind, ast);
+
+            if expanded_size(&ast)>Flags::default().expansion_limit {
+                return Err(self.error_between(ErrorKind::RepetitionTooLarge, s, remain));
+            }
+        }
+
+        Ok((ast,remain))
+    }
+
+    /*
+    '{' COUNT '}'
+    */
+    fn parse_counted(&self,s:&'a str)->Result<(RepetitionKind,&'a str),Error>{
+        let (_,after)=split_first(s).expect("caller checked for '{'");
+        let end=after.find('}').ok_or_else(|| self.error_between(ErrorKind::UnclosedCountedRepetition, s, ""))?;
+        let remain=&after[end+1..];
+        let kind=counted_kind(&after[..end]).ok_or_else(|| self.error_between(ErrorKind::InvalidCountedRepetition, s, remain))?;
+
+        Ok((kind,remain))
+    }
+
+    /*
+    PRIMARY -> '(' SIMILAR ')' | WILDCARD | BRACKET | ESCAPE char | char
+    */
+    fn parse_primary(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (c,after)=split_first(s).expect("caller checked for a char");
+
+        match c {
+            _ if Some(c)==self.escape=>{
+                let (escaped,after)=self.parse_escaped(s, after)?;
+                Ok((Ast::Literal(escaped),after))
+            }
+            '('=>{
+                let (ast,remain)=self.parse_similar(after)?;
+                match split_first(remain) {
+                    Some((')',after))=>Ok((ast,after)),
+                    _=>Err(self.error(ErrorKind::UnclosedGroup, s))
+                }
+            }
+            '*'|'+'|'?'|'{'=>Err(self.error(ErrorKind::DanglingRepetition, s)),
+            '%'=>Ok((any_string(),after)),
+            '_'=>Ok((Ast::Class(Class::All),after)),
+            '['=>self.parse_bracket(s),
+            _=>Ok((Ast::Literal(c),after))
+        }
+    }
+
+    /*
+    BRACKET -> '[' '^'? ITEM+ ']'
+    a ']' right after the opening bracket and a '-' at either end are literals
+    */
+    fn parse_bracket(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (_,mut remain)=split_first(s).expect("caller checked for '['");
+        let mut negated=false;
+        let mut set=ClassSet::empty();
+        let mut first=true;
+
+        if let Some(('^',after))=split_first(remain){
+            negated=true;
+            remain=after;
+        }
+
+        loop {
+            let item=remain;
+            if let Some((']',after))=split_first(remain).filter(|_| !first){
+                remain=after;
+                break;
+            }
+            first=false;
+
+            if let Some(name)=remain.strip_prefix("[:") {
+                let end=name.find(":]").ok_or_else(|| self.error(ErrorKind::UnclosedClass, s))?;
+                remain=&name[end+2..];
+                let posix=ClassSet::posix(&name[..end].to_ascii_lowercase())
+                .ok_or_else(|| self.error_between(ErrorKind::UnrecognizedPosixClass, item, remain))?;
+                set.union(&posix);
+                continue;
+            }
+
+            let start;
+            (start,remain)=self.parse_bracket_char(s, remain)?;
+            match split_first(remain) {
+                Some(('-',after)) if !after.is_empty()&&!after.starts_with(']')=>{
+                    let end;
+                    (end,remain)=self.parse_bracket_char(s, after)?;
+                    if end<start {
+                        return Err(self.error_between(ErrorKind::InvalidClassRange, item, remain));
+                    }
+                    set.push(ClassRange::new(start, end));
+                }
+                _=>set.push(ClassRange::new(start, start))
+            }
+        }
+
+        if negated {
+            set.negate();
+        }
+
+        Ok((Ast::Class(Class::Set(set)),remain))
+    }
+
+    fn parse_bracket_char(&self,s:&'a str,remain:&'a str)->Result<(char,&'a str),Error>{
+        match split_first(remain) {
+            Some((c,after)) if Some(c)==self.escape=>split_first(after).ok_or_else(|| self.error(ErrorKind::UnclosedClass, s)),
+            Some(next)=>Ok(next),
+            None=>Err(self.error(ErrorKind::UnclosedClass, s))
+        }
+    }
+}
+
+fn any_string()->Ast{
+    Ast::repetition(RepetitionKind::ZeroOrMore, Ast::Class(Class::All))
+}
+
+fn anchored(ast:Ast)->Ast{
+    Ast::Concat(vec![Ast::Look(Look::Start),ast,Ast::Look(Look::End)])
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn like(pattern:&str,input:&str)->bool{
+        SqlPattern::like(pattern, Some('\\')).unwrap().is_match(input)
+    }
+
+    fn similar(pattern:&str,input:&str)->bool{
+        SqlPattern::similar_to(pattern, Some('\\')).unwrap().is_match(input)
+    }
+
+    #[test]
+    fn parse_anchored(){
+        assert_eq!(parse_like("a_",None).unwrap(),Ast::Concat(vec![
+            Ast::Look(Look::Start),
+            Ast::Concat(vec![Ast::Literal('a'),Ast::Class(Class::All)]),
+            Ast::Look(Look::End)
+        ]));
+        assert_eq!(parse_similar_to("",None).unwrap(),anchored(Ast::Empty));
+    }
+
+    #[test]
+    fn like_wildcards(){
+        assert!(like("abc","abc"));
+        assert!(!like("abc","xabcx"));
+        assert!(like("a%","abc"));
+        assert!(like("a%","a"));
+        assert!(!like("a%","ba"));
+        assert!(like("%b%","abc"));
+        assert!(like("_b_","abc"));
+        assert!(!like("_b_","abcd"));
+        assert!(like("%","line\nbreak"));
+        assert!(like("a_c","a\nc"));
+        assert!(like("日_","日本"));
+        assert!(like("a.c*","a.c*"));
+        assert!(!like("a.c","abc"));
+    }
+
+    #[test]
+    fn like_escape(){
+        assert!(like("100\\%","100%"));
+        assert!(!like("100\\%","1000"));
+        assert!(like("a\\_b","a_b"));
+        assert!(!like("a\\_b","axb"));
+        assert!(like("a\\\\b","a\\b"));
+
+        let pattern=SqlPattern::like("50!%%", Some('!')).unwrap();
+        assert!(pattern.is_match("50% off"));
+        assert!(!pattern.is_match("500 off"));
+
+        let pattern=SqlPattern::like("a\\%", None).unwrap();
+        assert!(pattern.is_match("a\\xyz"));
+    }
+
+    #[test]
+    fn similar_to(){
+        assert!(similar("abc","abc"));
+        assert!(!similar("abc","abcd"));
+        assert!(similar("%(b|d)%","abc"));
+        assert!(!similar("(b|d)%","abc"));
+        assert!(similar("a|bc","bc"));
+        assert!(!similar("a|bc","abc"));
+        assert!(similar("(ab)+c?","ababc"));
+        assert!(similar("x{2,3}","xxx"));
+        assert!(!similar("x{2,3}","xxxx"));
+        assert!(similar("a.c","a.c"));
+        assert!(!similar("a.c","abc"));
+        assert!(similar("^a$","^a$"));
+        assert!(similar("_*","any\nthing"));
+        assert!(similar("a\\*\\(","a*("));
+    }
+
+    #[test]
+    fn similar_to_brackets(){
+        assert!(similar("[a-c]+","abcab"));
+        assert!(!similar("[a-c]+","abd"));
+        assert!(similar("[^0-9]%","x1"));
+        assert!(!similar("[^0-9]%","1x"));
+        assert!(similar("[]-]","]"));
+        assert!(similar("[]-]","-"));
+        assert!(similar("[[:DIGIT:]_]+","4_2"));
+        assert!(similar("[[:alpha:]]","Q"));
+        assert!(similar("[\\]]","]"));
+        assert!(similar("[%_]","%"));
+        assert!(!similar("[%_]","a"));
+    }
+
+    #[test]
+    fn errors(){
+        for (pattern,kind,span) in [
+            ("a\\",ErrorKind::EmptyEscape,1..2),
+            ("(ab",ErrorKind::UnclosedGroup,0..1),
+            ("ab)",ErrorKind::UnopenedGroup,2..3),
+            ("*a",ErrorKind::DanglingRepetition,0..1),
+            ("a|+",ErrorKind::DanglingRepetition,2..3),
+            ("a[bc",ErrorKind::UnclosedClass,1..2),
+            ("[z-a]",ErrorKind::InvalidClassRange,1..4),
+            ("[[:vowel:]]",ErrorKind::UnrecognizedPosixClass,1..10),
+            ("a{2",ErrorKind::UnclosedCountedRepetition,1..3),
+            ("a{3,2}",ErrorKind::InvalidCountedRepetition,1..6),
+            ("(a{100}){1000}",ErrorKind::RepetitionTooLarge,0..14),
+        ]{
+            let error=parse_similar_to(pattern,Some('\\')).unwrap_err();
+            assert_eq!(error.kind(),&kind,"{pattern}");
+            assert_eq!(error.span(),span,"{pattern}");
+        }
+
+        let error=parse_like("50%!",Some('!')).unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::EmptyEscape);
+        assert_eq!(error.span(),3..4);
+    }
+}
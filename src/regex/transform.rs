@@ -1,153 +1,1048 @@
-
-#[derive(Debug,PartialEq)]
-pub enum ThompsonOp  {
-    LeftParen,
-    RightParen,
-    Asterisk,
-    Or,
-    Concat,
-    All,
-    Primary(char)
-}
+use super::ast::{Ast,Backreference,LookAroundKind,Class,ClassRange,ClassSet,Look,PerlClass,PerlClassKind,RepetitionKind,UnicodeClass,UnicodeProperty};
+use super::error::{Error,ErrorKind};
+use std::cell::{Cell,RefCell};
 
 /*
-postfix grammer:
+grammer:
 
-REGEX -> OR+
-OR -> ASTERISK ('|' OR)?
-ASTERISK -> PRIMARY '*'?
-PRIMARY -> '('REGEX')' | CHAR_CLASS 
-CHAR_CLASS -> ('\' char) | '.' | char
+REGEX -> CONCAT ('|' CONCAT)*
+CONCAT -> (FLAGS ')' | REPETITION)*
+FLAGS -> '(?' flag* ('-' flag*)?
+REPETITION -> PRIMARY (QUANTIFIER ('?' | '+')?)*
+QUANTIFIER -> '*' | '+' | '?' | '{' COUNT '}'
+COUNT -> number (',' number?)?
+PRIMARY -> '(' NAME? REGEX ')' | FLAGS ':' REGEX ')' | LOOK_AROUND REGEX ')' | '(?>' REGEX ')' | '^' | '$' | CHAR_CLASS 
+NAME -> ('?P<' | '?<') name '>'
+LOOK_AROUND -> '(?=' | '(?!' | '(?<=' | '(?<!'
+CHAR_CLASS -> ESCAPE | '.' | BRACKET | char
+BRACKET -> '[' '^'? ITEMS (SET_OP ITEMS)* ']'
+ITEMS -> (POSIX_CLASS | BRACKET | ITEM)*
+ITEM -> BRACKET_CHAR ('-' BRACKET_CHAR)?
+SET_OP -> '&&' | '--' | '~~'
+POSIX_CLASS -> '[:' '^'? name ':]'
+BRACKET_CHAR -> ESCAPE | char
+ESCAPE -> '\' (UNICODE_CLASS | HEX | OCTAL | BACKREFERENCE | char)
+BACKREFERENCE -> digit+ | 'k<' name '>'
+UNICODE_CLASS -> ('p' | 'P') (char | '{' name '}')
+HEX -> 'x' hex hex | 'u' hex hex hex hex | ('x' | 'u') '{' hex+ '}'
+OCTAL -> '0' octal? octal?
+
+dialects:
+POSIX ERE has no escapes besides a '\' before a special char, no '(?' groups, lazy or possessive quantifiers
+and no set operators or nested brackets, a '\' inside a bracket is a plain char and [=c=] [.c.] name the char c.
+ECMAScript follows the 'u' flag, \d \w \b are ASCII only, [^] is any char, [] is no char and ']' always closes a bracket,
+\cX is a control char, '.' stops at every line terminator, flags only come scoped as in (?i:...),
+there are no atomic groups, possessive quantifiers, set operators, nested brackets, POSIX classes, \A \z or octal escapes
+and only the syntax chars ^$\.*+?()[]{}|/- can be escaped.
 */
 
-fn to_thompson_op(c:&char)->ThompsonOp{
-    match c {
-        '('=> ThompsonOp::LeftParen,
-        ')'=> ThompsonOp::RightParen,
-        '*'=> ThompsonOp::Asterisk,
-        '|'=> ThompsonOp::Or,
-        '.'=>ThompsonOp::All,
-        _=> ThompsonOp::Primary(*c)
-    }
+/*
+Which syntax a pattern is read in, every dialect parses into the same ast
+*/
+#[derive(Clone,Copy,Debug,Default,PartialEq, Eq)]
+pub enum Dialect{
+    /*this crate's own syntax with leftmost-first matching*/
+    #[default]
+    Native,
+    /*POSIX extended regular expressions, searches report the leftmost-longest match*/
+    Posix,
+    /*the syntax of JavaScript and JSON Schema patterns*/
+    EcmaScript
 }
 
-pub fn to_thompson_postfix(s:&str)->Result<Vec<ThompsonOp>,&str>{
-    Ok(to_postfix_concat(s)?.0)
+#[derive(Clone,Copy,Debug,PartialEq, Eq)]
+pub struct Flags{
+    /*\d \w \s \b use the Unicode definitions instead of ASCII only ones*/
+    pub unicode:bool,
+    /*^ and $ match at the start and end of every line instead of only the whole input*/
+    pub multi_line:bool,
+    /*literals and classes also match the chars simple case folding relates them to*/
+    pub case_insensitive:bool,
+    /*. matches \n as well*/
+    pub dot_matches_new_line:bool,
+    /*whitespace and # comments outside of brackets are not part of the pattern*/
+    pub ignore_whitespace:bool,
+    /*upper bound on the copies a counted repetition like (ab){100} expands to, measured in NFA transitions*/
+    pub expansion_limit:usize,
+    /*the syntax the pattern is written in, inline flags can't change it*/
+    pub dialect:Dialect
 }
 
-fn to_postfix_concat(s:&str)->Result<(Vec<ThompsonOp>,&str),&str>{
-    let (mut stack, mut remain)=to_postfix_or(s)?;
-    
-    while let Some(next_char)=remain.chars().next(){
-        match to_thompson_op(&next_char) {
-            ThompsonOp::RightParen=>{
-                break;
-            },
-            _=>{
-                let (right_concat,right_remain)=to_postfix_or(remain)?;
-                stack.extend(right_concat);
-                stack.push(ThompsonOp::Concat);
-                remain=right_remain;
-            }
+impl Default for Flags {
+    fn default() -> Self {
+        Flags{
+            unicode:true,
+            multi_line:false,
+            case_insensitive:false,
+            dot_matches_new_line:false,
+            ignore_whitespace:false,
+            expansion_limit:10_000,
+            dialect:Dialect::Native
         }
     }
+}
 
-    Ok((stack,remain))
+pub fn parse(s:&str)->Result<Ast,Error>{
+    parse_with_flags(s, Flags::default())
 }
 
-fn to_postfix_or(s:&str)->Result<(Vec<ThompsonOp>,&str),&str>{
-    let (mut stack, mut remain)=to_postfix_asterisk(s)?;
-    
-    if let Some(next_char)=remain.chars().next(){
-        if let ThompsonOp::Or= to_thompson_op(&next_char) {
-            let (right_concat,right_remain)=to_postfix_or(&remain[1..])?;
-            stack.extend(right_concat);
-            stack.push(ThompsonOp::Or);
+pub fn parse_with_flags(s:&str,flags:Flags)->Result<Ast,Error>{
+    Parser::new(s,flags).parse()
+}
+
+/*
+Holds the whole pattern so errors can point back into it,
+every parse function takes and returns the unparsed remainder.
+Inline flags change flags until the end of the enclosing group.
+*/
+struct Parser<'a>{
+    pattern:&'a str,
+    flags:Cell<Flags>,
+    /*capture groups opened so far and the names given to them*/
+    captures:Cell<usize>,
+    names:RefCell<Vec<(&'a str,usize)>>
+}
+
+/*
+What a backslash sequence stands for, brackets only accept some of these
+*/
+enum Escape{
+    Literal(char),
+    Class(Class),
+    Look(Look),
+    Backreference(usize)
+}
+
+impl<'a> Parser<'a> {
+    fn new(pattern:&'a str,flags:Flags)->Parser<'a>{
+        Parser{
+            pattern,
+            flags:Cell::new(flags),
+            captures:Cell::new(0),
+            names:RefCell::new(Vec::new())
+        }
+    }
+
+    fn flags(&self)->Flags{
+        self.flags.get()
+    }
+
+    fn dialect(&self)->Dialect{
+        self.flags().dialect
+    }
+
+    fn parse(&self)->Result<Ast,Error>{
+        let (ast,remain)=self.parse_or(self.pattern)?;
+        
+        match remain.chars().next() {
+            Some(_)=>Err(self.error(ErrorKind::UnopenedGroup, remain)),
+            None=>Ok(ast)
+        }
+    }
+
+    fn offset(&self,at:&str)->usize{
+        self.pattern.len()-at.len()
+    }
+
+    /*points at the char starting at*/
+    fn error(&self,kind:ErrorKind,at:&str)->Error{
+        let start=self.offset(at);
+        let end=start+at.chars().next().map_or(0, char::len_utf8);
+        Error::new(kind, self.pattern, start..end)
+    }
+
+    /*points from the start of from up to the start of to*/
+    fn error_between(&self,kind:ErrorKind,from:&str,to:&str)->Error{
+        Error::new(kind, self.pattern, self.offset(from)..self.offset(to))
+    }
+
+    fn parse_or(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (first, mut remain)=self.parse_concat(s)?;
+        let mut alternatives=vec![first];
+        
+        while let Some(('|',after))=split_first(remain){
+            let (right,right_remain)=self.parse_concat(after)?;
+            alternatives.push(right);
             remain=right_remain;
         }
+        
+        Ok((Ast::alternation(alternatives),remain))
     }
-    
-    Ok((stack,remain))
-}
 
-fn to_postfix_asterisk(s:&str)->Result<(Vec<ThompsonOp>,&str),&str>{
-    let (mut stack, mut remain)=to_postfix_primary(s)?;
-    
-    if let Some(next_char)=remain.chars().next(){
-        if let ThompsonOp::Asterisk= to_thompson_op(&next_char) {
-            stack.push(ThompsonOp::Asterisk);
-            remain=&remain[1..];
+    fn parse_concat(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let mut items=vec![];
+        let mut remain=self.skip_whitespace(s);
+        
+        while let Some(next_char)=remain.chars().next(){
+            match next_char {
+                ')'|'|'=>{
+                    break;
+                },
+                _=>{
+                    if let Some(after)=self.parse_set_flags(remain)? {
+                        remain=after;
+                    }
+                    else {
+                        let (item,item_remain)=self.parse_repetition(remain)?;
+                        items.push(item);
+                        remain=item_remain;
+                    }
+                }
+            }
+            remain=self.skip_whitespace(remain);
         }
+
+        Ok((Ast::concat(items),remain))
     }
-    
-    Ok((stack,remain))
-}
 
-fn to_postfix_primary(s:&str)->Result<(Vec<ThompsonOp>,&str),&str>{
-    let mut stack:Vec<ThompsonOp>=vec![];
-    let mut remain=s;
+    /*
+    REPETITION -> PRIMARY (QUANTIFIER ('?' | '+')?)*
+    stacked quantifiers apply from the inside out, a{2}* is (a{2})*,
+    a '?' right after a quantifier makes it lazy and a '+' makes it possessive instead of stacking,
+    a*+ is the atomic group (?>a*)
+    */
+    fn parse_repetition(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (mut ast, mut remain)=self.parse_primary(s)?;
+        
+        loop {
+            remain=self.skip_whitespace(remain);
+            let kind;
+            (kind,remain)=match split_first(remain) {
+                Some(('*',after))=>(RepetitionKind::ZeroOrMore,after),
+                Some(('+',after))=>(RepetitionKind::OneOrMore,after),
+                Some(('?',after))=>(RepetitionKind::ZeroOrOne,after),
+                Some(('{',_))=>self.parse_counted(remain)?,
+                _=>break
+            };
+            remain=self.skip_whitespace(remain);
+            ast=match split_first(remain) {
+                Some(('?'|'+',_)) if self.dialect()==Dialect::Posix=>Ast::repetition(kind, ast),
+                Some(('+',_)) if self.dialect()==Dialect::EcmaScript=>{
+                    return Err(self.error(ErrorKind::UnsupportedInDialect, remain));
+                }
+                Some(('?',after))=>{
+                    remain=after;
+                    Ast::lazy_repetition(kind, ast)
+                }
+                Some(('+',after))=>{
+                    remain=after;
+                    Ast::atomic(Ast::repetition(kind, ast))
+                }
+                _=>Ast::repetition(kind, ast)
+            };
 
-    if let Some(next_char)=remain.chars().next(){
-        match to_thompson_op(&next_char) {
-            ThompsonOp::LeftParen=>{
-                (stack,remain)=to_postfix_concat(&remain[1..])?;
-                remain=&remain[1..];
+            if expanded_size(&ast)>self.flags().expansion_limit {
+                return Err(self.error_between(ErrorKind::RepetitionTooLarge, s, remain));
             }
-            _=>{
-                (stack,remain)=to_postfix_char_class(s)?;
+        }
+        
+        Ok((ast,remain))
+    }
+
+    /*
+    '{' COUNT '}'
+    COUNT -> number (',' number?)?
+    */
+    fn parse_counted(&self,s:&'a str)->Result<(RepetitionKind,&'a str),Error>{
+        let (_,after)=split_first(s).expect("caller checked for '{'");
+        let end=after.find('}').ok_or_else(|| self.error_between(ErrorKind::UnclosedCountedRepetition, s, ""))?;
+        let (count,remain)=(&after[..end],&after[end+1..]);
+        let kind=counted_kind(count).ok_or_else(|| self.error_between(ErrorKind::InvalidCountedRepetition, s, remain))?;
+
+        Ok((kind,remain))
+    }
+
+    fn parse_primary(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let mut ast=Ast::Empty;
+        let mut remain=s;
+
+        if let Some((next_char,after))=split_first(remain){
+            match next_char {
+                '(' if self.dialect()==Dialect::Posix=>{
+                    (ast,remain)=self.parse_group(s, after, None)?;
+                }
+                '(' if self.dialect()==Dialect::EcmaScript&&(s.starts_with("(?>")||s.starts_with("(?P<"))=>{
+                    let end=if s.starts_with("(?>") {3} else {4};
+                    return Err(self.error_between(ErrorKind::UnsupportedInDialect, s, &s[end..]));
+                }
+                '(' if look_around_prefix(s).is_some()=>{
+                    (ast,remain)=self.parse_look_around(s)?;
+                }
+                '(' if s.starts_with("(?>")=>{
+                    let inner;
+                    (inner,remain)=self.parse_group_body(s, &s[3..])?;
+                    ast=Ast::atomic(inner);
+                }
+                '(' if starts_named_group(s)=>{
+                    let name_start=&after[after.find('<').expect("checked for '<'")+1..];
+                    let (name,name_remain)=self.parse_group_name(name_start)?;
+                    (ast,remain)=self.parse_group(s, name_remain, Some(name))?;
+                }
+                '(' if after.starts_with('?')=>{
+                    let outer_flags=self.flags();
+                    let flags;
+                    (flags,remain)=self.parse_flags(s)?;
+                    self.flags.set(flags);
+                    let inner;
+                    (inner,remain)=self.parse_or(&remain[1..])?;
+                    remain=match split_first(remain) {
+                        Some((')',after))=>after,
+                        _=>return Err(self.error(ErrorKind::UnclosedGroup, s))
+                    };
+                    self.flags.set(outer_flags);
+                    ast=inner;
+                }
+                '('=>{
+                    (ast,remain)=self.parse_group(s, after, None)?;
+                }
+                '^'=>{
+                    remain=after;
+                    ast=Ast::Look(if self.flags().multi_line {Look::StartLine} else {Look::Start});
+                }
+                '$'=>{
+                    remain=after;
+                    ast=Ast::Look(if self.flags().multi_line {Look::EndLine} else {Look::End});
+                }
+                '*'|'+'|'?'|'{'=>{
+                    return Err(self.error(ErrorKind::DanglingRepetition, s));
+                }
+                _=>{
+                    (ast,remain)=self.parse_char_class(s)?;
+                }
+            }  
+        }
+        
+        Ok((ast,remain))
+    }
+
+    /*
+    '(' NAME? REGEX ')'
+    inner starts after the name, the group gets the next index when it opens
+    */
+    fn parse_group(&self,s:&'a str,inner:&'a str,name:Option<&str>)->Result<(Ast,&'a str),Error>{
+        let index=self.captures.get()+1;
+        self.captures.set(index);
+
+        let (ast,remain)=self.parse_group_body(s, inner)?;
+        let ast=match name {
+            Some(name)=>Ast::named_group(index, name, ast),
+            None=>Ast::group(index, ast)
+        };
+        Ok((ast,remain))
+    }
+
+    /*
+    REGEX ')' of the group opened at s, flags set inside the group end with it
+    */
+    fn parse_group_body(&self,s:&'a str,inner:&'a str)->Result<(Ast,&'a str),Error>{
+        let outer_flags=self.flags();
+
+        let (ast,remain)=self.parse_or(inner)?;
+        let remain=match split_first(remain) {
+            Some((')',after))=>after,
+            _=>return Err(self.error(ErrorKind::UnclosedGroup, s))
+        };
+        self.flags.set(outer_flags);
+
+        Ok((ast,remain))
+    }
+
+    /*
+    LOOK_AROUND REGEX ')'
+    look-behinds have to have a bounded length
+    */
+    fn parse_look_around(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (kind,prefix)=look_around_prefix(s).expect("caller checked for a look-around");
+        let (ast,remain)=self.parse_group_body(s, &s[prefix..])?;
+
+        let behind=matches!(kind,LookAroundKind::Behind|LookAroundKind::NotBehind);
+        if behind&&ast.length_bounds().1.is_none() {
+            return Err(self.error_between(ErrorKind::UnboundedLookBehind, s, remain));
+        }
+
+        Ok((Ast::look_around(kind, ast),remain))
+    }
+
+    /*
+    name '>'
+    names start with a letter or '_' followed by letters, digits and '_', every name is used once
+    */
+    fn parse_group_name(&self,s:&'a str)->Result<(&'a str,&'a str),Error>{
+        let end=s.find('>').ok_or_else(|| self.error_between(ErrorKind::InvalidGroupName, s, ""))?;
+        let (name,remain)=(&s[..end],&s[end+1..]);
+        let name_end=&s[end..];
+
+        let mut chars=name.chars();
+        let valid=chars.next().is_some_and(|c| c.is_alphabetic()||c=='_')
+        &&chars.all(|c| c.is_alphanumeric()||c=='_');
+        if !valid {
+            return Err(self.error_between(ErrorKind::InvalidGroupName, s, name_end));
+        }
+        if self.names.borrow().iter().any(|(other,_)| *other==name) {
+            return Err(self.error_between(ErrorKind::DuplicateGroupName, s, name_end));
+        }
+        self.names.borrow_mut().push((name,self.captures.get()+1));
+
+        Ok((name,remain))
+    }
+
+    /*
+    FLAGS -> '(?' flag* ('-' flag*)?
+    flags after '-' are turned off, returns them with the remainder starting at the ')' or ':' that ends them,
+    (?:...) sets no flags and only groups
+    */
+    fn parse_flags(&self,s:&'a str)->Result<(Flags,&'a str),Error>{
+        let (_,remain)=split_first(s).expect("caller checked for '('");
+        let (_,mut remain)=split_first(remain).expect("caller checked for '?'");
+        let mut flags=self.flags();
+        let mut enable=true;
+        let mut empty=true;
+
+        loop {
+            let flag=remain;
+            let next_char;
+            (next_char,remain)=split_first(remain).ok_or_else(|| self.error(ErrorKind::UnclosedGroup, s))?;
+            let enabled=match next_char {
+                ')' if empty=>{
+                    return Err(self.error(ErrorKind::MissingFlags, flag));
+                }
+                ':' if empty&&!enable=>{
+                    return Err(self.error(ErrorKind::MissingFlags, flag));
+                }
+                ')'|':'=>{
+                    return Ok((flags,flag));
+                }
+                '-' if enable=>{
+                    enable=false;
+                    empty=true;
+                    continue;
+                }
+                'i'=>&mut flags.case_insensitive,
+                'm'=>&mut flags.multi_line,
+                's'=>&mut flags.dot_matches_new_line,
+                'x'|'u' if self.dialect()==Dialect::EcmaScript=>{
+                    return Err(self.error(ErrorKind::UnsupportedInDialect, flag));
+                }
+                'x'=>&mut flags.ignore_whitespace,
+                'u'=>&mut flags.unicode,
+                _=>{
+                    return Err(self.error(ErrorKind::UnrecognizedFlag, flag));
+                }
+            };
+            *enabled=enable;
+            empty=false;
+        }
+    }
+
+    /*
+    FLAGS ')'
+    sets the flags for the rest of the enclosing group, None when s does not start with one,
+    ECMAScript only has the scoped form
+    */
+    fn parse_set_flags(&self,s:&'a str)->Result<Option<&'a str>,Error>{
+        if !s.starts_with("(?")||starts_named_group(s)||look_around_prefix(s).is_some()||s.starts_with("(?>") {
+            return Ok(None);
+        }
+        if self.dialect()==Dialect::Posix {
+            return Ok(None);
+        }
+        let (flags,remain)=self.parse_flags(s)?;
+        match split_first(remain) {
+            Some((')',after)) if self.dialect()==Dialect::EcmaScript=>{
+                Err(self.error_between(ErrorKind::UnsupportedInDialect, s, after))
+            }
+            Some((')',after))=>{
+                self.flags.set(flags);
+                Ok(Some(after))
             }
-        }  
+            _=>Ok(None)
+        }
     }
-    
-    Ok((stack,remain))
-}
 
-/*
-CHAR_CLASS -> ('\' char) | '.' | char 
-*/
-fn to_postfix_char_class(s:&str)->Result<(Vec<ThompsonOp>,&str),&str>{
-    let mut stack:Vec<ThompsonOp>=vec![];
-    let mut remain=s;
+    /*in verbose mode skips whitespace and comments running from '#' to the end of the line*/
+    fn skip_whitespace(&self,s:&'a str)->&'a str{
+        if !self.flags().ignore_whitespace {
+            return s;
+        }
+        let mut remain=s;
+        loop {
+            remain=remain.trim_start();
+            match remain.strip_prefix('#') {
+                Some(comment)=>{
+                    remain=comment.find('\n').map_or("", |end| &comment[end+1..]);
+                }
+                None=>return remain
+            }
+        }
+    }
 
-    if let Some(next_char)=remain.chars().next(){
-        remain=&remain[1..];
+    /*a literal that case insensitivity widens into a class*/
+    fn literal(&self,c:char)->Ast{
+        if !self.flags().case_insensitive {
+            return Ast::Literal(c);
+        }
+        let mut set=ClassSet::from_char(c);
+        set.case_fold();
+        match set==ClassSet::from_char(c) {
+            true=>Ast::Literal(c),
+            false=>Ast::Class(Class::Set(set))
+        }
+    }
 
-        if next_char=='\\'{
-            let escaped=remain.chars().next().ok_or("escapted nothing")?;
-            remain=&remain[1..];
-            stack.push(ThompsonOp::Primary(escaped))
+    /*classes keep their kind unless case insensitivity adds chars to them*/
+    fn class(&self,class:Class)->Ast{
+        if !self.flags().case_insensitive||matches!(class,Class::All|Class::AllExceptNewline) {
+            return Ast::Class(class);
+        }
+        let set=class.to_set();
+        let mut folded=set.clone();
+        folded.case_fold();
+        match folded==set {
+            true=>Ast::Class(class),
+            false=>Ast::Class(Class::Set(folded))
         }
-        else {
-            match to_thompson_op(&next_char) {
-                ThompsonOp::All=>{
-                    stack.push(ThompsonOp::All)
+    }
+
+    /*
+    CHAR_CLASS -> ESCAPE | '.' | BRACKET | char 
+    */
+    fn parse_char_class(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let mut ast=Ast::Empty;
+        let mut remain=s;
+
+        if let Some((next_char,after))=split_first(remain){
+            remain=after;
+
+            match next_char {
+                '\\'=>{
+                    let escape;
+                    (escape,remain)=self.parse_escape(s)?;
+                    ast=match escape {
+                        Escape::Literal(c)=>self.literal(c),
+                        Escape::Class(class)=>self.class(class),
+                        Escape::Look(look)=>Ast::Look(look),
+                        Escape::Backreference(index)=>{
+                            Ast::Backreference(Backreference{index,case_insensitive:self.flags().case_insensitive})
+                        }
+                    };
+                }
+                '.' if self.flags().dot_matches_new_line=>{
+                    ast=Ast::Class(Class::All)
+                }
+                '.' if self.dialect()==Dialect::EcmaScript=>{
+                    let mut set=ClassSet::new(['\n','\r','\u{2028}','\u{2029}'].map(|c| ClassRange::new(c, c)));
+                    set.negate();
+                    ast=Ast::Class(Class::Set(set))
                 }
-                ThompsonOp::Primary(_)=>{
-                    stack.push(ThompsonOp::Primary(next_char))
+                '.'=>{
+                    ast=Ast::Class(Class::AllExceptNewline)
+                }
+                '['=>{
+                    (ast,remain)=self.parse_bracket(s)?;
+                }
+                ']'|'}' if self.dialect()==Dialect::EcmaScript=>{
+                    return Err(self.error(ErrorKind::UnsupportedInDialect, s));
                 }
                 _=>{
-                    panic!("Unexpected operation at char_charr parse");
+                    ast=self.literal(next_char)
                 }
             }  
         }
+        
+        Ok((ast,remain))
     }
-    
-    Ok((stack,remain))
+
+    /*
+    BRACKET -> '[' '^'? ITEMS (SET_OP ITEMS)* ']'
+    operators apply left to right and the negation applies to their result
+    */
+    fn parse_bracket(&self,s:&'a str)->Result<(Ast,&'a str),Error>{
+        let (set,remain)=self.parse_bracket_set(s)?;
+        Ok((Ast::Class(Class::Set(set)),remain))
+    }
+
+    fn parse_bracket_set(&self,s:&'a str)->Result<(ClassSet,&'a str),Error>{
+        let (_,mut remain)=split_first(s).expect("caller checked for '['");
+        let mut negated=false;
+
+        if let Some(('^',after))=split_first(remain){
+            negated=true;
+            remain=after;
+        }
+
+        if let Some((']',after))=split_first(remain).filter(|_| self.dialect()==Dialect::EcmaScript) {
+            let mut set=ClassSet::empty();
+            if negated {
+                set.negate();
+            }
+            return Ok((set,after));
+        }
+
+        let set;
+        (set,remain)=self.parse_bracket_items(s, remain, true)?;
+        let mut set=set.ok_or_else(|| self.error_between(ErrorKind::MissingClassOperand, remain, &remain[2..]))?;
+
+        while let Some(operator)=remain.get(..2).filter(|operator| is_set_operator(operator)) {
+            let at=remain;
+            let operand;
+            (operand,remain)=self.parse_bracket_items(s, &remain[2..], false)?;
+            let operand=operand.ok_or_else(|| self.error_between(ErrorKind::MissingClassOperand, at, &at[2..]))?;
+            match operator {
+                "&&"=>set.intersect(&operand),
+                "--"=>set.difference(&operand),
+                _=>set.symmetric_difference(&operand)
+            }
+        }
+
+        let (_,remain)=split_first(remain).expect("items stop at ']' or an operator");
+        if negated {
+            set.negate();
+        }
+
+        Ok((set,remain))
+    }
+
+    /*
+    ITEMS -> (POSIX_CLASS | BRACKET | ITEM)*
+    ITEM -> BRACKET_CHAR ('-' BRACKET_CHAR)?
+    stops before ']' or a set operator, a ']' right at the start of a bracket and a '-' at either end are literals,
+    a '-' after a class like \d is a literal as well, returns None when there were no items
+    */
+    fn parse_bracket_items(&self,s:&'a str,mut remain:&'a str,first:bool)->Result<(Option<ClassSet>,&'a str),Error>{
+        let mut ranges=Vec::new();
+        let mut empty=true;
+
+        loop {
+            let item=remain;
+            let start=match split_first(remain) {
+                None=>{
+                    return Err(self.error(ErrorKind::UnclosedClass, s));
+                }
+                Some((']',_)) if !(first&&empty)=>break,
+                _ if self.dialect()==Dialect::Native&&remain.get(..2).is_some_and(is_set_operator)=>break,
+                Some(('[',_)) if self.dialect()==Dialect::Posix=>{
+                    let set;
+                    (set,remain)=match self.parse_posix_class(remain)? {
+                        Some(posix)=>posix,
+                        None=>self.parse_posix_element(remain)
+                    };
+                    ranges.extend_from_slice(set.ranges());
+                    empty=false;
+                    continue;
+                }
+                Some(('[',_)) if self.dialect()==Dialect::Native=>{
+                    let set;
+                    (set,remain)=match self.parse_posix_class(remain)? {
+                        Some(posix)=>posix,
+                        None=>self.parse_bracket_set(remain)?
+                    };
+                    ranges.extend_from_slice(set.ranges());
+                    empty=false;
+                    continue;
+                }
+                Some(_)=>{
+                    let start;
+                    (start,remain)=self.parse_bracket_char(remain)?;
+                    start
+                }
+            };
+            empty=false;
+
+            let start=match start {
+                Escape::Literal(start)=>start,
+                Escape::Class(class)=>{
+                    ranges.extend_from_slice(class.to_set().ranges());
+                    continue;
+                }
+                Escape::Look(_)|Escape::Backreference(_)=>{
+                    return Err(self.error_between(ErrorKind::InvalidClassEscape, item, remain));
+                }
+            };
+
+            match split_first(remain) {
+                Some(('-',after)) if !after.is_empty()&&!after.starts_with(']')&&(self.dialect()!=Dialect::Native||!remain.starts_with("--"))=>{
+                    let end;
+                    (end,remain)=self.parse_bracket_char(after)?;
+                    match end {
+                        Escape::Literal(end) if start<=end=>{
+                            ranges.push(ClassRange::new(start, end));
+                        }
+                        _=>{
+                            return Err(self.error_between(ErrorKind::InvalidClassRange, item, remain));
+                        }
+                    }
+                }
+                _=>{
+                    ranges.push(ClassRange::new(start, start));
+                }
+            }
+        }
+
+        if empty {
+            return Ok((None,remain));
+        }
+        let mut set=ClassSet::new(ranges);
+        if self.flags().case_insensitive {
+            set.case_fold();
+        }
+
+        Ok((Some(set),remain))
+    }
+
+    /*
+    POSIX_CLASS -> '[:' '^'? name ':]'
+    returns None when s doesn't have that shape so it can be read as a nested bracket instead
+    */
+    fn parse_posix_class(&self,s:&'a str)->Result<Option<(ClassSet,&'a str)>,Error>{
+        let Some(inner)=s.strip_prefix("[:") else {
+            return Ok(None);
+        };
+        let Some(end)=inner.find(":]") else {
+            return Ok(None);
+        };
+        let (negated,name)=match inner[..end].strip_prefix('^') {
+            Some(name)=>(true,name),
+            None=>(false,&inner[..end])
+        };
+        if name.is_empty()||!name.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Ok(None);
+        }
+        let remain=&inner[end+2..];
+
+        let mut set=ClassSet::posix(name).ok_or_else(|| self.error_between(ErrorKind::UnrecognizedPosixClass, s, remain))?;
+        if negated {
+            set.negate();
+        }
+
+        Ok(Some((set,remain)))
+    }
+
+    /*
+    '[=' char '=]' | '[.' char '.]'
+    only single char collating elements exist here, anything else is a '[' taken literally
+    */
+    fn parse_posix_element(&self,s:&'a str)->(ClassSet,&'a str){
+        let mut chars=s.chars();
+        chars.next();
+        if let (Some(open@('='|'.')),Some(c),Some(close),Some(']'))=(chars.next(),chars.next(),chars.next(),chars.next()) {
+            if open==close {
+                return (ClassSet::from_char(c),chars.as_str());
+            }
+        }
+        (ClassSet::from_char('['),&s[1..])
+    }
+
+    /*
+    BRACKET_CHAR -> ESCAPE | char
+    POSIX brackets take '\\' literally and ECMAScript reads [\b] as a backspace
+    */
+    fn parse_bracket_char(&self,s:&'a str)->Result<(Escape,&'a str),Error>{
+        match split_first(s) {
+            Some(('\\',after)) if self.dialect()==Dialect::Posix=>Ok((Escape::Literal('\\'),after)),
+            Some(('\\',after)) if self.dialect()==Dialect::EcmaScript&&after.starts_with('b')=>Ok((Escape::Literal('\x08'),&after[1..])),
+            Some(('\\',_))=>self.parse_escape(s),
+            Some((c,after))=>Ok((Escape::Literal(c),after)),
+            None=>Err(self.error(ErrorKind::UnclosedClass, s))
+        }
+    }
+
+    /*
+    ESCAPE -> '\' (UNICODE_CLASS | HEX | OCTAL | BACKREFERENCE | char)
+    \d \w \s and their upper case negations are classes, \A \z \b \B are looks, \1 and \k<name> are backreferences,
+    \n \t \r \f \v \a name control chars, other letters and digits are errors so typos don't go unnoticed
+    and anything else is taken literally
+    */
+    fn parse_escape(&self,s:&'a str)->Result<(Escape,&'a str),Error>{
+        let (_,remain)=split_first(s).expect("caller checked for '\\'");
+        let (escaped,remain)=split_first(remain).ok_or_else(|| self.error(ErrorKind::EmptyEscape, s))?;
+        let perl=|kind,negated| Escape::Class(Class::Perl(PerlClass::new(kind, negated, self.flags().unicode)));
+
+        match self.dialect() {
+            Dialect::Posix if escaped.is_ascii_alphanumeric()=>{
+                return Err(self.error_between(ErrorKind::UnsupportedInDialect, s, remain));
+            }
+            Dialect::Posix=>{
+                return Ok((Escape::Literal(escaped),remain));
+            }
+            Dialect::EcmaScript=>{
+                return self.parse_ecma_escape(s);
+            }
+            Dialect::Native=>{}
+        }
+
+        let escape=match escaped {
+            'd'=>perl(PerlClassKind::Digit,false),
+            'D'=>perl(PerlClassKind::Digit,true),
+            'w'=>perl(PerlClassKind::Word,false),
+            'W'=>perl(PerlClassKind::Word,true),
+            's'=>perl(PerlClassKind::Space,false),
+            'S'=>perl(PerlClassKind::Space,true),
+            'A'=>Escape::Look(Look::Start),
+            'z'=>Escape::Look(Look::End),
+            'b' if self.flags().unicode=>Escape::Look(Look::WordBoundaryUnicode),
+            'B' if self.flags().unicode=>Escape::Look(Look::NotWordBoundaryUnicode),
+            'b'=>Escape::Look(Look::WordBoundaryAscii),
+            'B'=>Escape::Look(Look::NotWordBoundaryAscii),
+            'p'|'P'=>{
+                return self.parse_unicode_class(s);
+            }
+            'x'|'u'=>{
+                return self.parse_hex(s);
+            }
+            '1'..='9'|'k'=>{
+                return self.parse_backreference(s);
+            }
+            '0'=>{
+                let end=remain.bytes().take(2).take_while(|b| (b'0'..=b'7').contains(b)).count();
+                let (digits,remain)=remain.split_at(end);
+                let value=digits.bytes().fold(0,|value,digit| value*8+(digit-b'0'));
+                return Ok((Escape::Literal(char::from(value)),remain));
+            }
+            'n'=>Escape::Literal('\n'),
+            't'=>Escape::Literal('\t'),
+            'r'=>Escape::Literal('\r'),
+            'f'=>Escape::Literal('\x0C'),
+            'v'=>Escape::Literal('\x0B'),
+            'a'=>Escape::Literal('\x07'),
+            _ if escaped.is_ascii_alphanumeric()=>{
+                return Err(self.error_between(ErrorKind::UnrecognizedEscape, s, remain));
+            }
+            _=>Escape::Literal(escaped)
+        };
+
+        Ok((escape,remain))
+    }
+
+    /*
+    ESCAPE in ECMAScript, \d and \w are always ASCII and \s always Unicode,
+    \cX is X modulo 32 for an ASCII letter X and \0 can't start an octal escape
+    */
+    fn parse_ecma_escape(&self,s:&'a str)->Result<(Escape,&'a str),Error>{
+        let (_,remain)=split_first(s).expect("caller checked for '\\'");
+        let (escaped,after)=split_first(remain).ok_or_else(|| self.error(ErrorKind::EmptyEscape, s))?;
+        let perl=|kind,negated,unicode| Escape::Class(Class::Perl(PerlClass::new(kind, negated, unicode)));
+        let unsupported=|to| Err(self.error_between(ErrorKind::UnsupportedInDialect, s, to));
+
+        let escape=match escaped {
+            'd'=>perl(PerlClassKind::Digit,false,false),
+            'D'=>perl(PerlClassKind::Digit,true,false),
+            'w'=>perl(PerlClassKind::Word,false,false),
+            'W'=>perl(PerlClassKind::Word,true,false),
+            's'=>perl(PerlClassKind::Space,false,true),
+            'S'=>perl(PerlClassKind::Space,true,true),
+            'b'=>Escape::Look(Look::WordBoundaryAscii),
+            'B'=>Escape::Look(Look::NotWordBoundaryAscii),
+            'c'=>{
+                return match split_first(after) {
+                    Some((letter,after)) if letter.is_ascii_alphabetic()=>Ok((Escape::Literal(char::from(letter as u8%32)),after)),
+                    _=>Err(self.error_between(ErrorKind::UnrecognizedEscape, s, after))
+                };
+            }
+            '0' if after.starts_with(|c:char| c.is_ascii_digit())=>{
+                return unsupported(&after[1..]);
+            }
+            '0'=>Escape::Literal('\0'),
+            'x' if after.starts_with('{')=>{
+                return unsupported(after);
+            }
+            'p'|'P' if !after.starts_with('{')=>{
+                return unsupported(after);
+            }
+            'x'|'u'=>{
+                return self.parse_hex(s);
+            }
+            'p'|'P'=>{
+                return self.parse_unicode_class(s);
+            }
+            '1'..='9'|'k'=>{
+                return self.parse_backreference(s);
+            }
+            'A'|'z'|'a'=>{
+                return unsupported(after);
+            }
+            'n'=>Escape::Literal('\n'),
+            't'=>Escape::Literal('\t'),
+            'r'=>Escape::Literal('\r'),
+            'f'=>Escape::Literal('\x0C'),
+            'v'=>Escape::Literal('\x0B'),
+            '^'|'$'|'\\'|'.'|'*'|'+'|'?'|'('|')'|'['|']'|'{'|'}'|'|'|'/'|'-'=>Escape::Literal(escaped),
+            _ if escaped.is_ascii_alphanumeric()=>{
+                return Err(self.error_between(ErrorKind::UnrecognizedEscape, s, after));
+            }
+            _=>{
+                return unsupported(after);
+            }
+        };
+
+        Ok((escape,after))
+    }
+
+    /*
+    BACKREFERENCE -> digit+ | 'k<' name '>'
+    the group has to be opened before the backreference
+    */
+    fn parse_backreference(&self,s:&'a str)->Result<(Escape,&'a str),Error>{
+        let (_,remain)=split_first(s).expect("caller checked for '\\'");
+        let (index,remain)=match remain.strip_prefix("k<") {
+            Some(name_start)=>{
+                let end=name_start.find('>').ok_or_else(|| self.error_between(ErrorKind::InvalidGroupName, name_start, ""))?;
+                let name=&name_start[..end];
+                let remain=&name_start[end+1..];
+                let index=self.names.borrow().iter().find(|(other,_)| *other==name).map(|(_,index)| *index);
+                (index.ok_or_else(|| self.error_between(ErrorKind::InvalidBackreference, s, remain))?,remain)
+            }
+            None if remain.starts_with('k')=>{
+                return Err(self.error_between(ErrorKind::UnrecognizedEscape, s, &remain[1..]));
+            }
+            None=>{
+                let end=remain.bytes().take_while(u8::is_ascii_digit).count();
+                let index=remain[..end].parse::<usize>().ok().filter(|index| *index<=self.captures.get());
+                let remain=&remain[end..];
+                (index.ok_or_else(|| self.error_between(ErrorKind::InvalidBackreference, s, remain))?,remain)
+            }
+        };
+
+        Ok((Escape::Backreference(index),remain))
+    }
+
+    /*
+    HEX -> 'x' hex hex | 'u' hex hex hex hex | ('x' | 'u') '{' hex+ '}'
+    the braced form takes up to 8 digits naming any scalar value
+    */
+    fn parse_hex(&self,s:&'a str)->Result<(Escape,&'a str),Error>{
+        let (_,remain)=split_first(s).expect("caller checked for '\\'");
+        let (kind,remain)=split_first(remain).expect("caller checked for 'x' or 'u'");
+        let (digits,remain,max_len)=match split_first(remain) {
+            Some(('{',after))=>{
+                let end=after.find('}').ok_or_else(|| self.error_between(ErrorKind::InvalidHexEscape, s, ""))?;
+                (&after[..end],&after[end+1..],8)
+            }
+            _=>{
+                let len=if kind=='x' {2} else {4};
+                let end=remain.bytes().take(len).take_while(u8::is_ascii_hexdigit).count();
+                if end<len {
+                    return Err(self.error_between(ErrorKind::InvalidHexEscape, s, &remain[end..]));
+                }
+                (&remain[..end],&remain[end..],len)
+            }
+        };
+
+        let c=Some(digits)
+        .filter(|digits| !digits.is_empty()&&digits.len()<=max_len&&digits.bytes().all(|b| b.is_ascii_hexdigit()))
+        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+        .and_then(char::from_u32)
+        .ok_or_else(|| self.error_between(ErrorKind::InvalidHexEscape, s, remain))?;
+
+        Ok((Escape::Literal(c),remain))
+    }
+
+    /*
+    UNICODE_CLASS -> ('p' | 'P') (char | '{' name '}')
+    */
+    fn parse_unicode_class(&self,s:&'a str)->Result<(Escape,&'a str),Error>{
+        let (_,remain)=split_first(s).expect("caller checked for '\\'");
+        let (p,remain)=split_first(remain).expect("caller checked for 'p'");
+        let (name,remain)=match split_first(remain) {
+            Some(('{',after))=>{
+                let end=after.find('}').ok_or_else(|| self.error_between(ErrorKind::UnclosedUnicodeClass, s, ""))?;
+                (&after[..end],&after[end+1..])
+            }
+            Some((c,after))=>(&remain[..c.len_utf8()],after),
+            None=>return Err(self.error(ErrorKind::EmptyEscape, s))
+        };
+
+        let property=UnicodeProperty::from_name(name)
+        .ok_or_else(|| self.error_between(ErrorKind::UnicodePropertyNotFound, s, remain))?;
+
+        Ok((Escape::Class(Class::Unicode(UnicodeClass::new(property, p=='P'))),remain))
+    }
+}
+
+/*
+COUNT -> number (',' number?)?
+the text between the braces of a counted repetition, None unless it's valid with n <= m
+*/
+pub(crate) fn counted_kind(count:&str)->Option<RepetitionKind>{
+    let number=|digits:&str|{
+        Some(digits)
+        .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|digits| digits.parse::<u32>().ok())
+    };
+
+    match count.split_once(',') {
+        None=>Some(RepetitionKind::Exactly(number(count)?)),
+        Some((min,""))=>Some(RepetitionKind::AtLeast(number(min)?)),
+        Some((min,max))=>{
+            let (min,max)=(number(min)?,number(max)?);
+            (min<=max).then_some(RepetitionKind::Bounded(min, max))
+        }
+    }
+}
+
+/*
+transitions the NFA compiler builds for ast once counted repetitions are copied out
+*/
+pub(crate) fn expanded_size(ast:&Ast)->usize{
+    match ast {
+        Ast::Empty|Ast::Literal(_)|Ast::Class(_)|Ast::Look(_)|Ast::Backreference(_)=>1,
+        Ast::Concat(items)|Ast::Alternation(items)=>items.iter().map(expanded_size).sum(),
+        Ast::Group(group)=>expanded_size(&group.ast),
+        Ast::LookAround(look_around)=>expanded_size(&look_around.ast),
+        Ast::Atomic(ast)=>expanded_size(ast),
+        Ast::Repetition(repetition)=>{
+            let size=expanded_size(&repetition.ast);
+            match repetition.kind {
+                RepetitionKind::ZeroOrMore|RepetitionKind::OneOrMore|RepetitionKind::ZeroOrOne=>size,
+                RepetitionKind::Exactly(n)=>size.saturating_mul(n as usize),
+                RepetitionKind::AtLeast(n)=>size.saturating_mul(n.max(1) as usize),
+                RepetitionKind::Bounded(_,m)=>size.saturating_mul(m as usize),
+            }
+        }
+    }
+}
+
+fn is_set_operator(s:&str)->bool{
+    matches!(s,"&&"|"--"|"~~")
+}
+
+fn starts_named_group(s:&str)->bool{
+    (s.starts_with("(?P<")||s.starts_with("(?<"))&&look_around_prefix(s).is_none()
+}
+
+/*kind of look-around s starts with and the length of its opening*/
+fn look_around_prefix(s:&str)->Option<(LookAroundKind,usize)>{
+    [
+        ("(?=",LookAroundKind::Ahead),
+        ("(?!",LookAroundKind::NotAhead),
+        ("(?<=",LookAroundKind::Behind),
+        ("(?<!",LookAroundKind::NotBehind)
+    ]
+    .into_iter()
+    .find(|(prefix,_)| s.starts_with(prefix))
+    .map(|(prefix,kind)| (kind,prefix.len()))
+}
+
+/*
+splits off the first char, advancing by its utf8 length instead of a single byte
+*/
+pub(crate) fn split_first(s:&str)->Option<(char,&str)>{
+    let c=s.chars().next()?;
+    Some((c,&s[c.len_utf8()..]))
 }
 
 #[cfg(test)]
-mod thompson_postfix_tests{
+mod ast_tests{
     use super::*;
 
+    fn star(ast:Ast)->Ast{
+        Ast::repetition(RepetitionKind::ZeroOrMore, ast)
+    }
+
     #[test]
-    fn concat_test()->Result<(),&'static str>{
+    fn concat_test()->Result<(),Error>{
         let input="abcdefg";
-        let result=to_thompson_postfix(input)?;
-        let mut expect=vec![ThompsonOp::Primary('a')];
-        for c in input.chars().skip(1){
-            expect.push(ThompsonOp::Primary(c));
-            expect.push(ThompsonOp::Concat);
-        }
+        let result=parse(input)?;
+        let expect=Ast::Concat(input.chars().map(Ast::Literal).collect());
         
         assert_eq!(result,expect);
 
@@ -156,16 +1051,13 @@ mod thompson_postfix_tests{
 
     
     #[test]
-    fn or_test_1()->Result<(),&'static str>{
+    fn or_test_1()->Result<(),Error>{
         let input="ab|c";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Or,
-            ThompsonOp::Concat
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')]),
+            Ast::Literal('c')
+        ]);
 
         assert_eq!(result,expect);
 
@@ -173,16 +1065,43 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn or_test_2()->Result<(),&'static str>{
+    fn or_test_2()->Result<(),Error>{
         let input="a|bc";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Or,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Concat
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Literal('a'),
+            Ast::Concat(vec![Ast::Literal('b'),Ast::Literal('c')])
+        ]);
+
+        assert_eq!(result,expect);
+
+        Ok(())
+    }
+
+    #[test]
+    fn or_test_3()->Result<(),Error>{
+        let input="a|b|c";
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Literal('a'),
+            Ast::Literal('b'),
+            Ast::Literal('c')
+        ]);
+
+        assert_eq!(result,expect);
+
+        Ok(())
+    }
+
+    #[test]
+    fn or_test_4()->Result<(),Error>{
+        let input="ab|cd|e";
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')]),
+            Ast::Concat(vec![Ast::Literal('c'),Ast::Literal('d')]),
+            Ast::Literal('e')
+        ]);
 
         assert_eq!(result,expect);
 
@@ -190,17 +1109,13 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn asterisk_test_1()->Result<(),&'static str>{
+    fn asterisk_test_1()->Result<(),Error>{
         let input="ab*|c";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Or,
-            ThompsonOp::Concat
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Concat(vec![Ast::Literal('a'),star(Ast::Literal('b'))]),
+            Ast::Literal('c')
+        ]);
 
         assert_eq!(result,expect);
 
@@ -208,17 +1123,13 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn asterisk_test_2()->Result<(),&'static str>{
+    fn asterisk_test_2()->Result<(),Error>{
         let input="a|b*c";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Asterisk,
-            ThompsonOp::Or,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Concat
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Literal('a'),
+            Ast::Concat(vec![star(Ast::Literal('b')),Ast::Literal('c')])
+        ]);
 
         assert_eq!(result,expect);
 
@@ -226,17 +1137,13 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn paren_test_1()->Result<(),&'static str>{
+    fn paren_test_1()->Result<(),Error>{
         let input="a|(b*c)";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Concat,
-            ThompsonOp::Or,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Literal('a'),
+            Ast::group(1,Ast::Concat(vec![star(Ast::Literal('b')),Ast::Literal('c')]))
+        ]);
 
         assert_eq!(result,expect);
 
@@ -244,17 +1151,13 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn paren_test_2()->Result<(),&'static str>{
+    fn paren_test_2()->Result<(),Error>{
         let input="(ab)*|c";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Concat,
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Or,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            star(Ast::group(1,Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')]))),
+            Ast::Literal('c')
+        ]);
 
         assert_eq!(result,expect);
 
@@ -262,24 +1165,17 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn paren_test_3()->Result<(),&'static str>{
+    fn paren_test_3()->Result<(),Error>{
         let input="12(ab)*|c#*";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('1'),
-            ThompsonOp::Primary('2'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Concat,
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Or,
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('#'),
-            ThompsonOp::Asterisk,
-            ThompsonOp::Concat,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Concat(vec![
+                Ast::Literal('1'),
+                Ast::Literal('2'),
+                star(Ast::group(1,Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')])))
+            ]),
+            Ast::Concat(vec![Ast::Literal('c'),star(Ast::Literal('#'))])
+        ]);
 
         assert_eq!(result,expect);
 
@@ -287,24 +1183,17 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn paren_test_4()->Result<(),&'static str>{
+    fn paren_test_4()->Result<(),Error>{
         let input="12(ab)*|(c#)*";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('1'),
-            ThompsonOp::Primary('2'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Concat,
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Primary('#'),
-            ThompsonOp::Concat,
-            ThompsonOp::Asterisk,
-            ThompsonOp::Or,
-            ThompsonOp::Concat,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Concat(vec![
+                Ast::Literal('1'),
+                Ast::Literal('2'),
+                star(Ast::group(1,Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')])))
+            ]),
+            star(Ast::group(2,Ast::Concat(vec![Ast::Literal('c'),Ast::Literal('#')])))
+        ]);
 
         assert_eq!(result,expect);
 
@@ -312,74 +1201,761 @@ mod thompson_postfix_tests{
     }
 
     #[test]
-    fn char_class_test_1()->Result<(),&'static str>{
+    fn empty_test()->Result<(),Error>{
+        assert_eq!(parse("")?,Ast::Empty);
+        assert_eq!(parse("()")?,Ast::group(1,Ast::Empty));
+        assert_eq!(parse("a|")?,Ast::Alternation(vec![Ast::Literal('a'),Ast::Empty]));
+        assert_eq!(parse("|a")?,Ast::Alternation(vec![Ast::Empty,Ast::Literal('a')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn utf8_test_1()->Result<(),Error>{
+        assert_eq!(parse("é*")?,star(Ast::Literal('é')));
+        assert_eq!(parse("日本")?,Ast::Concat(vec![Ast::Literal('日'),Ast::Literal('本')]));
+        assert_eq!(parse("a|日本*")?,Ast::Alternation(vec![
+            Ast::Literal('a'),
+            Ast::Concat(vec![Ast::Literal('日'),star(Ast::Literal('本'))])
+        ]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn utf8_test_2()->Result<(),Error>{
+        assert_eq!(parse("\\é")?,Ast::Literal('é'));
+        assert_eq!(parse("\\日*本")?,Ast::Concat(vec![star(Ast::Literal('日')),Ast::Literal('本')]));
+        assert_eq!(parse("\\🦀.")?,Ast::Concat(vec![Ast::Literal('🦀'),Ast::Class(Class::AllExceptNewline)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn utf8_test_3()->Result<(),Error>{
+        assert_eq!(parse("(日本)*")?,star(Ast::group(1,Ast::Concat(vec![Ast::Literal('日'),Ast::Literal('本')]))));
+        assert_eq!(parse("(é|ü)*ß")?,Ast::Concat(vec![
+            star(Ast::group(1,Ast::Alternation(vec![Ast::Literal('é'),Ast::Literal('ü')]))),
+            Ast::Literal('ß')
+        ]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn utf8_error_test(){
+        let error=parse("日(本").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedGroup);
+        assert_eq!(error.span(),3..4);
+
+        let error=parse("日本)").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnopenedGroup);
+        assert_eq!(error.span(),6..7);
+
+        let error=parse("é|*").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::DanglingRepetition);
+        assert_eq!(error.span(),3..4);
+    }
+
+    fn set(ranges:&[(char,char)])->Ast{
+        Ast::Class(Class::Set(ClassSet::new(ranges.iter().map(|(start,end)|ClassRange::new(*start, *end)))))
+    }
+
+    #[test]
+    fn bracket_test_1()->Result<(),Error>{
+        assert_eq!(parse("[abc]")?,set(&[('a','c')]));
+        assert_eq!(parse("[a-z0-9_]")?,set(&[('0','9'),('_','_'),('a','z')]));
+        assert_eq!(parse("[c-ea-d]")?,set(&[('a','e')]));
+        assert_eq!(parse("[é日]")?,set(&[('é','é'),('日','日')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bracket_test_2()->Result<(),Error>{
+        assert_eq!(parse("[]a]")?,set(&[(']',']'),('a','a')]));
+        assert_eq!(parse("[^]a]")?,parse("[^a\\]]")?);
+        assert_eq!(parse("[-a]")?,set(&[('-','-'),('a','a')]));
+        assert_eq!(parse("[a-]")?,set(&[('-','-'),('a','a')]));
+        assert_eq!(parse("[\\]\\-\\\\]")?,set(&[('-','-'),('\\','\\'),(']',']')]));
+        assert_eq!(parse("[\\^]")?,set(&[('^','^')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bracket_test_3()->Result<(),Error>{
+        let mut expect=ClassSet::new([ClassRange::new('a','z')]);
+        expect.negate();
+        assert_eq!(parse("[^a-z]")?,Ast::Class(Class::Set(expect)));
+        assert_eq!(parse("[^a-z]*|[.]")?,Ast::Alternation(vec![
+            star(parse("[^a-z]")?),
+            set(&[('.','.')])
+        ]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn posix_class_test()->Result<(),Error>{
+        assert_eq!(parse("[[:digit:]]")?,set(&[('0','9')]));
+        assert_eq!(parse("[[:alpha:]_]")?,set(&[('A','Z'),('_','_'),('a','z')]));
+        assert_eq!(parse("[[:xdigit:][:space:]]")?,set(&[('\t','\r'),(' ',' '),('0','9'),('A','F'),('a','f')]));
+        assert_eq!(parse("[^[:^ascii:]]")?,set(&[('\0','\x7F')]));
+        assert_eq!(parse("(?i)[[:upper:]]")?,set(&[('A','Z'),('a','z'),('\u{17F}','\u{17F}'),('\u{212A}','\u{212A}')]));
+        assert_eq!(parse("[:a]")?,set(&[(':',':'),('a','a')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn class_set_operation_test()->Result<(),Error>{
+        assert_eq!(parse("[a-z&&[^aeiou]]")?,set(&[('b','d'),('f','h'),('j','n'),('p','t'),('v','z')]));
+        assert_eq!(parse("[a-z--m-z]")?,set(&[('a','l')]));
+        assert_eq!(parse("[a-m~~h-z]")?,set(&[('a','g'),('n','z')]));
+        assert_eq!(parse("[[:word:]&&[:digit:]--5]")?,set(&[('0','4'),('6','9')]));
+        assert_eq!(parse("[^a-c&&b-d]")?,parse("[^bc]")?);
+        assert_eq!(parse("[[:alpha:]--[a-y]]")?,set(&[('A','Z'),('z','z')]));
+        assert_eq!(parse("[&a~-]")?,set(&[('&','&'),('-','-'),('a','a'),('~','~')]));
+
+        let letters=parse("[\\p{L}&&[^a-z]]")?;
+        let Ast::Class(Class::Set(letters))=letters else { panic!("expected a set") };
+        assert!(letters.contains('A')&&letters.contains('é')&&!letters.contains('q')&&!letters.contains('1'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bracket_error_test(){
+        let error=parse("a[bc").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedClass);
+        assert_eq!(error.span(),1..2);
+
+        let error=parse("[]").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedClass);
+
+        let error=parse("[az-a]").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::InvalidClassRange);
+        assert_eq!(error.span(),2..5);
+
+        let error=parse("[a\\").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::EmptyEscape);
+        assert_eq!(error.span(),2..3);
+
+        for (input,kind,span) in [
+            ("[[:alpah:]]",ErrorKind::UnrecognizedPosixClass,1..10),
+            ("[&&a]",ErrorKind::MissingClassOperand,1..3),
+            ("[a--]",ErrorKind::MissingClassOperand,2..4),
+            ("[a&&~~b]",ErrorKind::MissingClassOperand,2..4),
+            ("[a[b]",ErrorKind::UnclosedClass,0..1),
+        ]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+    }
+
+    fn perl(kind:PerlClassKind,negated:bool)->Ast{
+        Ast::Class(Class::Perl(PerlClass::new(kind, negated, true)))
+    }
+
+    #[test]
+    fn perl_class_test_1()->Result<(),Error>{
+        assert_eq!(parse("\\d")?,perl(PerlClassKind::Digit,false));
+        assert_eq!(parse("\\W*")?,star(perl(PerlClassKind::Word,true)));
+        assert_eq!(parse("\\s|\\S")?,Ast::Alternation(vec![
+            perl(PerlClassKind::Space,false),
+            perl(PerlClassKind::Space,true)
+        ]));
+
+        let ascii=Flags{unicode:false,..Flags::default()};
+        assert_eq!(parse_with_flags("\\D", ascii)?,Ast::Class(Class::Perl(PerlClass::new(PerlClassKind::Digit, true, false))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn perl_class_test_2()->Result<(),Error>{
+        let ascii=Flags{unicode:false,..Flags::default()};
+        assert_eq!(parse_with_flags("[\\d_]", ascii)?,set(&[('0','9'),('_','_')]));
+        assert_eq!(parse_with_flags("[\\s-]", ascii)?,set(&[('\t','\r'),(' ',' '),('-','-')]));
+        assert_eq!(parse_with_flags("[\\d-z]", ascii)?,set(&[('-','-'),('0','9'),('z','z')]));
+
+        let mut expect=ClassSet::new([ClassRange::new('\t','\r'),ClassRange::new(' ',' ')]);
+        expect.negate();
+        assert_eq!(parse_with_flags("[^\\s]", ascii)?,Ast::Class(Class::Set(expect.clone())));
+        assert_eq!(parse_with_flags("[\\S]", ascii)?,Ast::Class(Class::Set(expect)));
+
+        let Ast::Class(unicode_word)=parse("[\\w]")? else {panic!("expected a class")};
+        assert!(unicode_word.contains(&'é'));
+        assert!(unicode_word.contains(&'٣'));
+        assert!(!unicode_word.contains(&'-'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn perl_class_error_test(){
+        let error=parse("[a-\\d]").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::InvalidClassRange);
+        assert_eq!(error.span(),1..5);
+    }
+
+    fn unicode(property:UnicodeProperty,negated:bool)->Ast{
+        Ast::Class(Class::Unicode(UnicodeClass::new(property, negated)))
+    }
+
+    #[test]
+    fn unicode_class_test_1()->Result<(),Error>{
+        assert_eq!(parse("\\p{L}")?,unicode(UnicodeProperty::GeneralCategory("Letter"),false));
+        assert_eq!(parse("\\pL")?,unicode(UnicodeProperty::GeneralCategory("Letter"),false));
+        assert_eq!(parse("\\p{Lu}")?,unicode(UnicodeProperty::GeneralCategory("Uppercase_Letter"),false));
+        assert_eq!(parse("\\p{uppercase letter}")?,unicode(UnicodeProperty::GeneralCategory("Uppercase_Letter"),false));
+        assert_eq!(parse("\\P{N}")?,unicode(UnicodeProperty::GeneralCategory("Number"),true));
+        assert_eq!(parse("\\p{Greek}")?,unicode(UnicodeProperty::Script("Greek"),false));
+        assert_eq!(parse("\\p{sc=Grek}")?,unicode(UnicodeProperty::Script("Greek"),false));
+        assert_eq!(parse("\\p{gc:Nd}*")?,star(unicode(UnicodeProperty::GeneralCategory("Decimal_Number"),false)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unicode_class_test_2()->Result<(),Error>{
+        let Ast::Class(class)=parse("[\\p{Greek}\\d]")? else {panic!("expected a class")};
+        assert!(class.contains(&'λ'));
+        assert!(class.contains(&'7'));
+        assert!(!class.contains(&'a'));
+
+        let Ast::Class(class)=parse("[^\\p{L}]")? else {panic!("expected a class")};
+        assert!(class.contains(&'7'));
+        assert!(!class.contains(&'日'));
+
+        let Ast::Class(class)=parse("[\\P{Lu}]")? else {panic!("expected a class")};
+        assert!(class.contains(&'a'));
+        assert!(!class.contains(&'Ä'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unicode_class_error_test(){
+        let error=parse("a\\p{Klingon}").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnicodePropertyNotFound);
+        assert_eq!(error.span(),1..12);
+
+        let error=parse("\\p{L").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedUnicodeClass);
+        assert_eq!(error.span(),0..4);
+
+        let error=parse("\\pZ\\p").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::EmptyEscape);
+        assert_eq!(error.span(),3..4);
+    }
+
+    #[test]
+    fn error_test_1(){
+        let error=parse("a(b|c").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedGroup);
+        assert_eq!(error.span(),1..2);
+
+        let error=parse("a((b)").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedGroup);
+        assert_eq!(error.span(),1..2);
+    }
+
+    #[test]
+    fn error_test_2(){
+        let error=parse("ab)c").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnopenedGroup);
+        assert_eq!(error.span(),2..3);
+    }
+
+    #[test]
+    fn error_test_3(){
+        for (input,at) in [("*a",0),("a|*",2),("(*)",1),("+a",0),("a|?",2),("({2})",1)]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&ErrorKind::DanglingRepetition);
+            assert_eq!(error.span(),at..at+1);
+        }
+    }
+
+    #[test]
+    fn repetition_test_1()->Result<(),Error>{
+        let repetition=|kind,ast| Ast::repetition(kind, ast);
+        let input="a+b?c{3}d{2,}e{2,5}";
+        let result=parse(input)?;
+        let expect=Ast::Concat(vec![
+            repetition(RepetitionKind::OneOrMore,Ast::Literal('a')),
+            repetition(RepetitionKind::ZeroOrOne,Ast::Literal('b')),
+            repetition(RepetitionKind::Exactly(3),Ast::Literal('c')),
+            repetition(RepetitionKind::AtLeast(2),Ast::Literal('d')),
+            repetition(RepetitionKind::Bounded(2,5),Ast::Literal('e')),
+        ]);
+
+        assert_eq!(result,expect);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repetition_test_2()->Result<(),Error>{
+        let input="(ab){2}*+|c**{1,}";
+        let result=parse(input)?;
+        let ab=Ast::group(1,Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')]));
+        let expect=Ast::Alternation(vec![
+            Ast::atomic(star(Ast::repetition(RepetitionKind::Exactly(2),ab))),
+            Ast::repetition(RepetitionKind::AtLeast(1),star(star(Ast::Literal('c'))))
+        ]);
+
+        assert_eq!(result,expect);
+
+        Ok(())
+    }
+
+    #[test]
+    fn lazy_repetition_test()->Result<(),Error>{
+        let input="a*?b+?c??d{2,3}?e??f*??";
+        let result=parse(input)?;
+        let lazy=|kind,c| Ast::lazy_repetition(kind, Ast::Literal(c));
+        let expect=Ast::Concat(vec![
+            lazy(RepetitionKind::ZeroOrMore,'a'),
+            lazy(RepetitionKind::OneOrMore,'b'),
+            lazy(RepetitionKind::ZeroOrOne,'c'),
+            lazy(RepetitionKind::Bounded(2,3),'d'),
+            lazy(RepetitionKind::ZeroOrOne,'e'),
+            Ast::repetition(RepetitionKind::ZeroOrOne,lazy(RepetitionKind::ZeroOrMore,'f')),
+        ]);
+
+        assert_eq!(result,expect);
+
+        Ok(())
+    }
+
+    #[test]
+    fn possessive_repetition_test()->Result<(),Error>{
+        let possessive=|kind,c| Ast::atomic(Ast::repetition(kind, Ast::Literal(c)));
+        assert_eq!(parse("a*+b++c?+d{2,}+")?,Ast::Concat(vec![
+            possessive(RepetitionKind::ZeroOrMore,'a'),
+            possessive(RepetitionKind::OneOrMore,'b'),
+            possessive(RepetitionKind::ZeroOrOne,'c'),
+            possessive(RepetitionKind::AtLeast(2),'d'),
+        ]));
+        assert_eq!(parse("a+++")?,Ast::repetition(RepetitionKind::OneOrMore,possessive(RepetitionKind::OneOrMore,'a')));
+        assert_eq!(parse("(?>a|(b))c")?,Ast::Concat(vec![
+            Ast::atomic(Ast::Alternation(vec![Ast::Literal('a'),Ast::group(1,Ast::Literal('b'))])),
+            Ast::Literal('c')
+        ]));
+        assert_eq!(parse("(?>(?i)a)a")?,Ast::Concat(vec![Ast::atomic(set(&[('A','A'),('a','a')])),Ast::Literal('a')]));
+        assert!(parse("a*+")?.needs_backtracking());
+
+        let error=parse("(?>a").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedGroup);
+        assert_eq!(error.span(),0..1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repetition_error_test(){
+        let error=parse("ab{2").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::UnclosedCountedRepetition);
+        assert_eq!(error.span(),2..4);
+
+        for (input,span) in [("a{}",1..3),("a{x}",1..4),("a{,2}",1..5),("a{3,2}b",1..6),("a{99999999999}",1..14)]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&ErrorKind::InvalidCountedRepetition);
+            assert_eq!(error.span(),span);
+        }
+
+        let error=parse("x(ab{100}){101}").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::RepetitionTooLarge);
+        assert_eq!(error.span(),1..15);
+
+        let small=Flags{expansion_limit:8,..Flags::default()};
+        assert!(parse_with_flags("a{8}", small).is_ok());
+        assert_eq!(parse_with_flags("a{2}{5}", small).unwrap_err().kind(),&ErrorKind::RepetitionTooLarge);
+    }
+
+    #[test]
+    fn anchor_test()->Result<(),Error>{
+        let look=Ast::Look;
+        assert_eq!(parse("^a$")?,Ast::Concat(vec![look(Look::Start),Ast::Literal('a'),look(Look::End)]));
+        assert_eq!(parse("\\Aa|\\z")?,Ast::Alternation(vec![
+            Ast::Concat(vec![look(Look::Start),Ast::Literal('a')]),
+            look(Look::End)
+        ]));
+
+        let multi_line=Flags{multi_line:true,..Flags::default()};
+        assert_eq!(parse_with_flags("^\\A$", multi_line)?,Ast::Concat(vec![look(Look::StartLine),look(Look::Start),look(Look::EndLine)]));
+
+        let error=parse("[a\\z]").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::InvalidClassEscape);
+        assert_eq!(error.span(),2..4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn word_boundary_test()->Result<(),Error>{
+        let look=Ast::Look;
+        assert_eq!(parse("\\ba\\B")?,Ast::Concat(vec![look(Look::WordBoundaryUnicode),Ast::Literal('a'),look(Look::NotWordBoundaryUnicode)]));
+
+        let ascii=Flags{unicode:false,..Flags::default()};
+        assert_eq!(parse_with_flags("\\b|\\B", ascii)?,Ast::Alternation(vec![look(Look::WordBoundaryAscii),look(Look::NotWordBoundaryAscii)]));
+
+        let error=parse("[\\b]").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::InvalidClassEscape);
+
+        Ok(())
+    }
+
+    #[test]
+    fn case_insensitive_test()->Result<(),Error>{
+        assert_eq!(parse("(?i)a1")?,Ast::Concat(vec![set(&[('A','A'),('a','a')]),Ast::Literal('1')]));
+        assert_eq!(parse("(?i)k")?,set(&[('K','K'),('k','k'),('\u{212A}','\u{212A}')]));
+        assert_eq!(parse("(?i)[a-c]")?,set(&[('A','C'),('a','c')]));
+        assert_eq!(parse("(?i)[^a-z]")?,Ast::Class(Class::Set({
+            let mut expect=ClassSet::new([ClassRange::new('A','Z'),ClassRange::new('a','z'),ClassRange::new('\u{17F}','\u{17F}'),ClassRange::new('\u{212A}','\u{212A}')]);
+            expect.negate();
+            expect
+        })));
+        assert_eq!(parse("(?i)\\d")?,perl(PerlClassKind::Digit,false));
+
+        let case_insensitive=Flags{case_insensitive:true,..Flags::default()};
+        assert_eq!(parse_with_flags("a(?-i)a", case_insensitive)?,Ast::Concat(vec![set(&[('A','A'),('a','a')]),Ast::Literal('a')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn case_insensitive_scope_test()->Result<(),Error>{
+        let ci_b=set(&[('B','B'),('b','b')]);
+        assert_eq!(parse("(a(?i)b)b")?,Ast::Concat(vec![
+            Ast::group(1,Ast::Concat(vec![Ast::Literal('a'),ci_b.clone()])),
+            Ast::Literal('b')
+        ]));
+        assert_eq!(parse("a(?i)b|b")?,Ast::Alternation(vec![
+            Ast::Concat(vec![Ast::Literal('a'),ci_b.clone()]),
+            ci_b
+        ]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn flags_test()->Result<(),Error>{
+        let dot=Ast::Class(Class::AllExceptNewline);
+        let any=Ast::Class(Class::All);
+        assert_eq!(parse(".(?s).")?,Ast::Concat(vec![dot.clone(),any.clone()]));
+        assert_eq!(parse("(?s:.).")?,Ast::Concat(vec![any.clone(),dot.clone()]));
+        assert_eq!(parse("(?m)^(?-m)^")?,Ast::Concat(vec![Ast::Look(Look::StartLine),Ast::Look(Look::Start)]));
+        assert_eq!(parse("(?ims:a)$")?,Ast::Concat(vec![set(&[('A','A'),('a','a')]),Ast::Look(Look::End)]));
+        assert_eq!(parse("(?i:a)*b")?,Ast::Concat(vec![star(set(&[('A','A'),('a','a')])),Ast::Literal('b')]));
+        assert_eq!(parse("(?-u:\\d\\b)\\d")?,Ast::Concat(vec![
+            Ast::Concat(vec![Ast::Class(Class::Perl(PerlClass::new(PerlClassKind::Digit, false, false))),Ast::Look(Look::WordBoundaryAscii)]),
+            perl(PerlClassKind::Digit,false)
+        ]));
+
+        let dot_all=Flags{dot_matches_new_line:true,..Flags::default()};
+        assert_eq!(parse_with_flags(".(?-s).", dot_all)?,Ast::Concat(vec![any,dot]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn verbose_test()->Result<(),Error>{
+        let input="(?x) a b # comment ( |
+            c * | \\  [ ]  #";
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b'),star(Ast::Literal('c'))]),
+            Ast::Concat(vec![Ast::Literal(' '),set(&[(' ',' ')])])
+        ]);
+
+        assert_eq!(result,expect);
+        assert_eq!(parse("a(?x: b c )d e")?,Ast::Concat(vec![
+            Ast::Literal('a'),
+            Ast::Concat(vec![Ast::Literal('b'),Ast::Literal('c')]),
+            Ast::Literal('d'),
+            Ast::Literal(' '),
+            Ast::Literal('e')
+        ]));
+        assert_eq!(parse("(?x)a{2} ?")?,Ast::lazy_repetition(RepetitionKind::Exactly(2), Ast::Literal('a')));
+
+        Ok(())
+    }
+
+    #[test]
+    fn flag_error_test(){
+        for (input,kind,span) in [
+            ("a(?q)",ErrorKind::UnrecognizedFlag,3..4),
+            ("(?i-)",ErrorKind::MissingFlags,4..5),
+            ("(?)",ErrorKind::MissingFlags,2..3),
+            ("(?i-i-i)",ErrorKind::UnrecognizedFlag,5..6),
+            ("(?i",ErrorKind::UnclosedGroup,0..1),
+            ("(?i)*",ErrorKind::DanglingRepetition,4..5),
+            ("(?-:a)",ErrorKind::MissingFlags,3..4),
+            ("(?s:a",ErrorKind::UnclosedGroup,0..1),
+        ]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+    }
+
+    #[test]
+    fn group_test()->Result<(),Error>{
+        let ab=Ast::Concat(vec![Ast::Literal('a'),Ast::Literal('b')]);
+        assert_eq!(parse("((a)b)")?,Ast::group(1,Ast::Concat(vec![Ast::group(2,Ast::Literal('a')),Ast::Literal('b')])));
+        assert_eq!(parse("(?:ab)(c)")?,Ast::Concat(vec![ab.clone(),Ast::group(1,Ast::Literal('c'))]));
+        assert_eq!(parse("(?P<first>ab)|(?<_2nd>c)")?,Ast::Alternation(vec![
+            Ast::named_group(1, "first", ab),
+            Ast::named_group(2, "_2nd", Ast::Literal('c'))
+        ]));
+        assert_eq!(parse("(a)(?:b)(?<x>c)")?.capture_names(),vec![None,None,Some("x".to_string())]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn group_error_test(){
+        for (input,kind,span) in [
+            ("(?P<1a>b)",ErrorKind::InvalidGroupName,4..6),
+            ("(?<>b)",ErrorKind::InvalidGroupName,3..3),
+            ("(?<a-b>c)",ErrorKind::InvalidGroupName,3..6),
+            ("(?<ab",ErrorKind::InvalidGroupName,3..5),
+            ("(?<a>b)(?P<a>c)",ErrorKind::DuplicateGroupName,11..12),
+            ("(?<a>b",ErrorKind::UnclosedGroup,0..1),
+        ]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+    }
+
+    #[test]
+    fn error_test_4(){
+        let error=parse("ab\\").unwrap_err();
+        assert_eq!(error.kind(),&ErrorKind::EmptyEscape);
+        assert_eq!(error.span(),2..3);
+    }
+
+    #[test]
+    fn escape_test()->Result<(),Error>{
+        let literals=|s:&str| Ast::Concat(s.chars().map(Ast::Literal).collect());
+        assert_eq!(parse("\\n\\t\\r\\f\\v\\a")?,literals("\n\t\r\x0C\x0B\x07"));
+        assert_eq!(parse("\\x41\\x{1F600}\\u00e9\\u{3b1}")?,literals("A😀éα"));
+        assert_eq!(parse("\\0\\012\\0778")?,literals("\0\n?8"));
+        assert_eq!(parse("\\.\\\\\\-\\é")?,literals(".\\-é"));
+        assert_eq!(parse("[\\x00-\\x{7F}\\t]")?,set(&[('\0','\x7F')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn backreference_test()->Result<(),Error>{
+        assert_eq!(parse("(a)\\1")?,Ast::Concat(vec![Ast::group(1,Ast::Literal('a')),Ast::backreference(1)]));
+        assert_eq!(parse("(?<x>a)(b)\\k<x>\\2")?,Ast::Concat(vec![
+            Ast::named_group(1, "x", Ast::Literal('a')),
+            Ast::group(2,Ast::Literal('b')),
+            Ast::backreference(1),
+            Ast::backreference(2)
+        ]));
+        assert_eq!(parse("(?i)(a)\\1")?,Ast::Concat(vec![
+            Ast::group(1,set(&[('A','A'),('a','a')])),
+            Ast::Backreference(Backreference{index:1,case_insensitive:true})
+        ]));
+        assert!(parse("(a)\\1")?.needs_backtracking());
+        assert!(!parse("(a)\\01")?.needs_backtracking());
+
+        for (input,kind,span) in [
+            ("(a)\\2",ErrorKind::InvalidBackreference,3..5),
+            ("\\1(a)",ErrorKind::InvalidBackreference,0..2),
+            ("(?<x>a)\\k<y>",ErrorKind::InvalidBackreference,7..12),
+            ("(a)[\\1]",ErrorKind::InvalidClassEscape,4..6),
+        ]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn look_around_test()->Result<(),Error>{
+        assert_eq!(parse("a(?=b)")?,Ast::Concat(vec![Ast::Literal('a'),Ast::look_around(LookAroundKind::Ahead, Ast::Literal('b'))]));
+        assert_eq!(parse("(?!a|b)")?,Ast::look_around(LookAroundKind::NotAhead, Ast::Alternation(vec![Ast::Literal('a'),Ast::Literal('b')])));
+        assert_eq!(parse("(?<=(a)b{2})")?,Ast::look_around(LookAroundKind::Behind, Ast::Concat(vec![
+            Ast::group(1,Ast::Literal('a')),
+            Ast::repetition(RepetitionKind::Exactly(2), Ast::Literal('b'))
+        ])));
+        assert_eq!(parse("(?<!(?i)a)a")?,Ast::Concat(vec![
+            Ast::look_around(LookAroundKind::NotBehind, set(&[('A','A'),('a','a')])),
+            Ast::Literal('a')
+        ]));
+        assert_eq!(parse("(?<x>a)")?,Ast::named_group(1, "x", Ast::Literal('a')));
+        assert!(parse("(?=a)")?.needs_backtracking());
+
+        for (input,kind,span) in [
+            ("a(?<=b+)",ErrorKind::UnboundedLookBehind,1..8),
+            ("(?<!(a)\\1)",ErrorKind::UnboundedLookBehind,0..10),
+            ("(?=a",ErrorKind::UnclosedGroup,0..1),
+        ]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn escape_error_test(){
+        for (input,kind,span) in [
+            ("a\\q",ErrorKind::UnrecognizedEscape,1..3),
+            ("[\\y]",ErrorKind::UnrecognizedEscape,1..3),
+            ("\\k",ErrorKind::UnrecognizedEscape,0..2),
+            ("\\x4",ErrorKind::InvalidHexEscape,0..3),
+            ("\\xg1",ErrorKind::InvalidHexEscape,0..2),
+            ("\\u12x4",ErrorKind::InvalidHexEscape,0..4),
+            ("\\x{}",ErrorKind::InvalidHexEscape,0..4),
+            ("\\x{D800}",ErrorKind::InvalidHexEscape,0..8),
+            ("\\x{110000}",ErrorKind::InvalidHexEscape,0..10),
+            ("\\u{41",ErrorKind::InvalidHexEscape,0..5),
+        ]{
+            let error=parse(input).unwrap_err();
+            assert_eq!(error.kind(),&kind);
+            assert_eq!(error.span(),span);
+        }
+    }
+
+    #[test]
+    fn posix_dialect_test()->Result<(),Error>{
+        let posix=Flags{dialect:Dialect::Posix,..Flags::default()};
+        assert_eq!(parse_with_flags("(a)*?", posix)?,Ast::repetition(RepetitionKind::ZeroOrOne, star(Ast::group(1,Ast::Literal('a')))));
+        assert_eq!(parse_with_flags("\\.\\(", posix)?,Ast::Concat(vec![Ast::Literal('.'),Ast::Literal('(')]));
+        assert_eq!(parse_with_flags("[\\d]", posix)?,set(&[('\\','\\'),('d','d')]));
+        assert_eq!(parse_with_flags("[a&&b~~]", posix)?,set(&[('&','&'),('a','b'),('~','~')]));
+        assert_eq!(parse_with_flags("[[.-.][=x=]]", posix)?,set(&[('-','-'),('x','x')]));
+        assert_eq!(parse_with_flags("[[a]", posix)?,set(&[('[','['),('a','a')]));
+        assert_eq!(parse_with_flags("[]x]", posix)?,set(&[(']',']'),('x','x')]));
+        assert_eq!(parse_with_flags("[[:digit:]]", posix)?,set(&[('0','9')]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ecma_dialect_test()->Result<(),Error>{
+        let ecma=Flags{dialect:Dialect::EcmaScript,..Flags::default()};
+        let ascii=|kind,negated| Ast::Class(Class::Perl(PerlClass::new(kind, negated, false)));
+        assert_eq!(parse_with_flags("\\d\\W", ecma)?,Ast::Concat(vec![ascii(PerlClassKind::Digit,false),ascii(PerlClassKind::Word,true)]));
+        assert_eq!(parse_with_flags("\\s", ecma)?,perl(PerlClassKind::Space,false));
+        assert_eq!(parse_with_flags("\\b", ecma)?,Ast::Look(Look::WordBoundaryAscii));
+        assert_eq!(parse_with_flags("[^]", ecma)?,Ast::Class(Class::Set(ClassSet::full())));
+        assert_eq!(parse_with_flags("[]", ecma)?,Ast::Class(Class::Set(ClassSet::empty())));
+        assert_eq!(parse_with_flags("\\cJ\\cm", ecma)?,Ast::Concat(vec![Ast::Literal('\n'),Ast::Literal('\r')]));
+        assert_eq!(parse_with_flags("[\\b\\cA]", ecma)?,set(&[('\x01','\x01'),('\x08','\x08')]));
+        assert_eq!(parse_with_flags("\\0\\/", ecma)?,Ast::Concat(vec![Ast::Literal('\0'),Ast::Literal('/')]));
+        assert_eq!(parse_with_flags("[a&&[b]", ecma)?,set(&[('&','&'),('[','['),('a','b')]));
+        assert_eq!(parse_with_flags("(?<x>a)\\k<x>", ecma)?,Ast::Concat(vec![
+            Ast::named_group(1, "x", Ast::Literal('a')),
+            Ast::backreference(1)
+        ]));
+        assert_eq!(parse_with_flags("(?i:a)", ecma)?,set(&[('A','A'),('a','a')]));
+
+        let Ast::Class(dot)=parse_with_flags(".", ecma)? else {
+            panic!("'.' is a class");
+        };
+        assert!(dot.contains(&'a'));
+        assert!(!dot.contains(&'\r'));
+        assert!(!dot.contains(&'\u{2028}'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn dialect_error_test(){
+        let posix=Flags{dialect:Dialect::Posix,..Flags::default()};
+        let ecma=Flags{dialect:Dialect::EcmaScript,..Flags::default()};
+        for (input,flags,kind,span) in [
+            ("a\\d",posix,ErrorKind::UnsupportedInDialect,1..3),
+            ("(a)\\1",posix,ErrorKind::UnsupportedInDialect,3..5),
+            ("(?:a)",posix,ErrorKind::DanglingRepetition,1..2),
+            ("(?i)a",posix,ErrorKind::DanglingRepetition,1..2),
+            ("(?i)a",ecma,ErrorKind::UnsupportedInDialect,0..4),
+            ("(?x:a)",ecma,ErrorKind::UnsupportedInDialect,2..3),
+            ("(?>a)",ecma,ErrorKind::UnsupportedInDialect,0..3),
+            ("(?P<x>a)",ecma,ErrorKind::UnsupportedInDialect,0..4),
+            ("a*+",ecma,ErrorKind::UnsupportedInDialect,2..3),
+            ("\\A",ecma,ErrorKind::UnsupportedInDialect,0..2),
+            ("\\01",ecma,ErrorKind::UnsupportedInDialect,0..3),
+            ("\\x{41}",ecma,ErrorKind::UnsupportedInDialect,0..2),
+            ("\\pL",ecma,ErrorKind::UnsupportedInDialect,0..2),
+            ("\\%",ecma,ErrorKind::UnsupportedInDialect,0..2),
+            ("\\c1",ecma,ErrorKind::UnrecognizedEscape,0..2),
+            ("a]",ecma,ErrorKind::UnsupportedInDialect,1..2),
+            ("[]a]",ecma,ErrorKind::UnsupportedInDialect,3..4),
+        ]{
+            let error=parse_with_flags(input, flags).unwrap_err();
+            assert_eq!(error.kind(),&kind,"{input}");
+            assert_eq!(error.span(),span,"{input}");
+        }
+    }
+
+    #[test]
+    fn char_class_test_1()->Result<(),Error>{
         let input=".*abc";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::All,
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Concat,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Concat(vec![
+            star(Ast::Class(Class::AllExceptNewline)),
+            Ast::Literal('a'),
+            Ast::Literal('b'),
+            Ast::Literal('c')
+        ]);
 
         assert_eq!(result,expect);
 
         Ok(())
     }
     #[test]
-    fn char_class_test_2()->Result<(),&'static str>{
+    fn char_class_test_2()->Result<(),Error>{
         let input="\\.*abc";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('.'),
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Concat,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Concat(vec![
+            star(Ast::Literal('.')),
+            Ast::Literal('a'),
+            Ast::Literal('b'),
+            Ast::Literal('c')
+        ]);
 
         assert_eq!(result,expect);
 
         Ok(())
     }
     #[test]
-    fn char_class_test_3()->Result<(),&'static str>{
+    fn char_class_test_3()->Result<(),Error>{
         let input="\\\\*abc";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('\\'),
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('a'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Concat,
-            ThompsonOp::Primary('c'),
-            ThompsonOp::Concat,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Concat(vec![
+            star(Ast::Literal('\\')),
+            Ast::Literal('a'),
+            Ast::Literal('b'),
+            Ast::Literal('c')
+        ]);
 
         assert_eq!(result,expect);
 
         Ok(())
     }
     #[test]
-    fn char_class_test_4()->Result<(),&'static str>{
+    fn char_class_test_4()->Result<(),Error>{
         let input="(a.)*|b";
-        let result=to_thompson_postfix(input)?;
-        let expect=vec![
-            ThompsonOp::Primary('a'),
-            ThompsonOp::All,
-            ThompsonOp::Concat,
-            ThompsonOp::Asterisk,
-            ThompsonOp::Primary('b'),
-            ThompsonOp::Or,
-        ];
+        let result=parse(input)?;
+        let expect=Ast::Alternation(vec![
+            star(Ast::group(1,Ast::Concat(vec![Ast::Literal('a'),Ast::Class(Class::AllExceptNewline)]))),
+            Ast::Literal('b')
+        ]);
 
         assert_eq!(result,expect);
 
@@ -387,4 +1963,4 @@ mod thompson_postfix_tests{
     }
 
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,67 @@
+use super::ast::{ClassRange,ClassSet};
+
+/*
+Tables generated offline from the Unicode Character Database,
+the data is covered by unicode_tables/LICENSE-UNICODE
+*/
+pub mod case_folding_simple;
+pub mod general_category;
+pub mod perl_decimal;
+pub mod perl_space;
+pub mod perl_word;
+pub mod property_values;
+pub mod script;
+
+pub fn table_contains(table:&[(char,char)],c:char)->bool{
+    table
+    .binary_search_by(|(start,end)|{
+        if *end<c {
+            std::cmp::Ordering::Less
+        }
+        else if *start>c {
+            std::cmp::Ordering::Greater
+        }
+        else {
+            std::cmp::Ordering::Equal
+        }
+    })
+    .is_ok()
+}
+
+pub fn table_to_set(table:&[(char,char)])->ClassSet{
+    ClassSet::new(table.iter().map(|(start,end)| ClassRange::new(*start, *end)))
+}
+
+/*entries of the case folding table for the chars from start to end*/
+pub fn case_folds_between(start:char,end:char)->&'static [(char,&'static [char])]{
+    let table=case_folding_simple::CASE_FOLDING_SIMPLE;
+    let from=table.partition_point(|(c,_)| *c<start);
+    let to=table.partition_point(|(c,_)| *c<=end);
+    &table[from..to]
+}
+
+/*looks up a name in one of the sorted BY_NAME tables*/
+pub fn by_name(tables:&[(&str,&'static [(char,char)])],name:&str)->Option<&'static [(char,char)]>{
+    tables
+    .binary_search_by(|(table_name,_)| (*table_name).cmp(name))
+    .ok()
+    .map(|i| tables[i].1)
+}
+
+/*
+Finds the canonical name for a loosely written property value,
+case, spaces, '_' and '-' are ignored as in UAX44-LM3
+*/
+pub fn canonical_name(aliases:&[(&str,&'static str)],name:&str)->Option<&'static str>{
+    let normalized:String=
+    name
+    .chars()
+    .filter(|c| !matches!(c,' '|'_'|'-'))
+    .flat_map(char::to_lowercase)
+    .collect();
+
+    aliases
+    .binary_search_by(|(alias,_)| (*alias).cmp(normalized.as_str()))
+    .ok()
+    .map(|i| aliases[i].1)
+}
@@ -0,0 +1,15 @@
+// Generated offline from the Unicode Character Database 16.0.0, do not edit by hand.
+// Regenerate with: ucd-generate property-bool ucd-16.0.0 --chars --include whitespace
+
+pub const WHITE_SPACE:&[(char,char)]=&[
+    ('\t', '\r'),
+    (' ', ' '),
+    ('\u{85}', '\u{85}'),
+    ('\u{a0}', '\u{a0}'),
+    ('\u{1680}', '\u{1680}'),
+    ('\u{2000}', '\u{200a}'),
+    ('\u{2028}', '\u{2029}'),
+    ('\u{202f}', '\u{202f}'),
+    ('\u{205f}', '\u{205f}'),
+    ('\u{3000}', '\u{3000}'),
+];
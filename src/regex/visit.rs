@@ -0,0 +1,204 @@
+use super::ast::{Ast,Group,LookAround,Repetition};
+
+/*
+Walks an ast by reference, visit_pre sees a node before its children and visit_post after them,
+the first error stops the walk and is returned from visit
+*/
+pub trait Visitor {
+    type Output;
+    type Err;
+
+    fn visit_pre(&mut self,_ast:&Ast)->Result<(),Self::Err>{
+        Ok(())
+    }
+
+    fn visit_post(&mut self,_ast:&Ast)->Result<(),Self::Err>{
+        Ok(())
+    }
+
+    /*what visit returns once every node was seen*/
+    fn finish(self)->Result<Self::Output,Self::Err>;
+}
+
+/*
+Rebuilds an ast by value. fold_pre can replace a node before its children are folded,
+the children of the node it returns are folded next, and fold_post can replace a node
+once its children are done. Both keep the node by default.
+Group indices are left as they are, a fold that adds or removes groups has to renumber them
+*/
+pub trait Fold {
+    type Err;
+
+    fn fold_pre(&mut self,ast:Ast)->Result<Ast,Self::Err>{
+        Ok(ast)
+    }
+
+    fn fold_post(&mut self,ast:Ast)->Result<Ast,Self::Err>{
+        Ok(ast)
+    }
+}
+
+pub fn visit<V:Visitor>(ast:&Ast,mut visitor:V)->Result<V::Output,V::Err>{
+    walk(ast, &mut visitor)?;
+    visitor.finish()
+}
+
+pub fn fold<F:Fold>(ast:Ast,folder:&mut F)->Result<Ast,F::Err>{
+    let ast=folder.fold_pre(ast)?;
+    let fold_box=|ast:Box<Ast>,folder:&mut F| fold(*ast, folder).map(Box::new);
+
+    let ast=match ast {
+        Ast::Empty|Ast::Literal(_)|Ast::Class(_)|Ast::Look(_)|Ast::Backreference(_)=>ast,
+        Ast::Concat(items)=>Ast::Concat(fold_items(items, folder)?),
+        Ast::Alternation(items)=>Ast::Alternation(fold_items(items, folder)?),
+        Ast::Repetition(Repetition{kind,greedy,ast})=>Ast::Repetition(Repetition{kind,greedy,ast:fold_box(ast, folder)?}),
+        Ast::Group(Group{index,name,ast})=>Ast::Group(Group{index,name,ast:fold_box(ast, folder)?}),
+        Ast::LookAround(LookAround{kind,ast})=>Ast::LookAround(LookAround{kind,ast:fold_box(ast, folder)?}),
+        Ast::Atomic(ast)=>Ast::Atomic(fold_box(ast, folder)?),
+    };
+
+    folder.fold_post(ast)
+}
+
+fn walk<V:Visitor>(ast:&Ast,visitor:&mut V)->Result<(),V::Err>{
+    visitor.visit_pre(ast)?;
+    match ast {
+        Ast::Empty|Ast::Literal(_)|Ast::Class(_)|Ast::Look(_)|Ast::Backreference(_)=>{},
+        Ast::Concat(items)|Ast::Alternation(items)=>{
+            for item in items {
+                walk(item, visitor)?;
+            }
+        },
+        Ast::Repetition(Repetition{ast,..})|Ast::Group(Group{ast,..})|Ast::LookAround(LookAround{ast,..})|Ast::Atomic(ast)=>{
+            walk(ast, visitor)?;
+        },
+    }
+    visitor.visit_post(ast)
+}
+
+fn fold_items<F:Fold>(items:Vec<Ast>,folder:&mut F)->Result<Vec<Ast>,F::Err>{
+    items.into_iter().map(|item| fold(item, folder)).collect()
+}
+
+
+#[cfg(test)]
+mod tests{
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::regex::ast::RepetitionKind;
+    use crate::regex::printer::print;
+    use crate::regex::transform::parse;
+
+    /*names the nodes in the order a visitor sees them*/
+    struct Trace(Vec<String>);
+
+    impl Visitor for Trace {
+        type Output=Vec<String>;
+        type Err=();
+
+        fn visit_pre(&mut self,ast:&Ast)->Result<(),()>{
+            self.0.push(format!("pre {}",print(ast)));
+            Ok(())
+        }
+
+        fn visit_post(&mut self,ast:&Ast)->Result<(),()>{
+            self.0.push(format!("post {}",print(ast)));
+            Ok(())
+        }
+
+        fn finish(self)->Result<Vec<String>,()>{
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn visit_order(){
+        let trace=visit(&parse("a(b)*").unwrap(), Trace(Vec::new())).unwrap();
+
+        assert_eq!(trace,[
+            "pre a(b)*","pre a","post a","pre (b)*","pre (b)","pre b","post b","post (b)","post (b)*","post a(b)*"
+        ]);
+    }
+
+    /*rejects unbounded repetitions the way a service might limit user patterns*/
+    struct NoUnbounded;
+
+    impl Visitor for NoUnbounded {
+        type Output=();
+        type Err=String;
+
+        fn visit_pre(&mut self,ast:&Ast)->Result<(),String>{
+            match ast {
+                Ast::Repetition(repetition) if repetition.kind.bounds().1.is_none()=>Err(print(ast)),
+                _=>Ok(())
+            }
+        }
+
+        fn finish(self)->Result<(),String>{
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn visit_stops_at_error(){
+        assert_eq!(visit(&parse("a{1,3}(b|c?)").unwrap(), NoUnbounded),Ok(()));
+        assert_eq!(visit(&parse("a{1,3}(b|c+)d*").unwrap(), NoUnbounded),Err("c+".to_string()));
+    }
+
+    /*empty named groups stand for the sub-pattern of that name*/
+    struct Substitute(HashMap<&'static str,Ast>);
+
+    impl Fold for Substitute {
+        type Err=String;
+
+        fn fold_pre(&mut self,ast:Ast)->Result<Ast,String>{
+            match ast {
+                Ast::Group(Group{name:Some(name),ast,..}) if *ast==Ast::Empty=>{
+                    self.0.get(name.as_str()).cloned().ok_or(name)
+                }
+                _=>Ok(ast)
+            }
+        }
+    }
+
+    #[test]
+    fn fold_substitutes(){
+        let definitions=HashMap::from([
+            ("octet",parse("[0-9]{1,3}").unwrap()),
+            ("dot",parse("\\.").unwrap())
+        ]);
+        let ast=parse("^(?<octet>)(?<dot>)x").unwrap();
+        let folded=fold(ast, &mut Substitute(definitions.clone())).unwrap();
+        assert_eq!(print(&folded),"^[0-9]{1,3}\\.x");
+
+        let missing=fold(parse("(?<port>)").unwrap(), &mut Substitute(definitions));
+        assert_eq!(missing,Err("port".to_string()));
+    }
+
+    /*post order sees children that were already rewritten*/
+    struct Double;
+
+    impl Fold for Double {
+        type Err=();
+
+        fn fold_post(&mut self,ast:Ast)->Result<Ast,()>{
+            match ast {
+                Ast::Literal(c)=>Ok(Ast::repetition(RepetitionKind::Exactly(2), Ast::Literal(c))),
+                Ast::Repetition(repetition) if repetition.kind==RepetitionKind::Exactly(2)=>{
+                    match *repetition.ast {
+                        Ast::Repetition(inner)=>Ok(Ast::repetition(RepetitionKind::Exactly(4), *inner.ast)),
+                        ast=>Ok(Ast::repetition(RepetitionKind::Exactly(2), ast))
+                    }
+                }
+                _=>Ok(ast)
+            }
+        }
+    }
+
+    #[test]
+    fn fold_post_order(){
+        let folded=fold(parse("a{2}|(b)").unwrap(), &mut Double).unwrap();
+        assert_eq!(print(&folded),"a{4}|(b{2})");
+    }
+}